@@ -1,6 +1,8 @@
 from __future__ import annotations
 
-from typing import Optional, List, Type
+import builtins
+
+from typing import Any, AsyncIterator, Dict, Iterator, Optional, List, Tuple, Type, Union
 
 class Theme:
     Characters: Theme
@@ -44,7 +46,23 @@ class Language:
     def from_str(cls: Type[Language], language: str) -> Language:
         ...
 
+class GameState:
+    NotStarted: GameState
+    InProgress: GameState
+    Guessing: GameState
+    Won: GameState
+    Lost: GameState
+    Expired: GameState
+    Closed: GameState
+
 class Guess:
+    def to_dict(self) -> Dict[str, Any]:
+        ...
+
+    @classmethod
+    def from_dict(cls: Type[Guess], data: Dict[str, Any]) -> Guess:
+        ...
+
     @property
     def id(self) -> str:
         ...
@@ -81,6 +99,259 @@ class Guess:
     def absolute_picture_path(self) -> str:
         ...
 
+class HistoryEntry:
+    def to_dict(self) -> Dict[str, Any]:
+        ...
+
+    @classmethod
+    def from_dict(cls: Type[HistoryEntry], data: Dict[str, Any]) -> HistoryEntry:
+        ...
+
+    @property
+    def step(self) -> int:
+        ...
+
+    @property
+    def question(self) -> str:
+        ...
+
+    @property
+    def answer(self) -> Answer:
+        ...
+
+    @property
+    def progression_before(self) -> float:
+        ...
+
+    @property
+    def progression_after(self) -> float:
+        ...
+
+    @property
+    def timestamp(self) -> float:
+        ...
+
+class GameResult:
+    def to_dict(self) -> Dict[str, Any]:
+        ...
+
+    @classmethod
+    def from_dict(cls: Type[GameResult], data: Dict[str, Any]) -> GameResult:
+        ...
+
+    @property
+    def guess(self) -> Guess:
+        ...
+
+    @property
+    def step(self) -> int:
+        ...
+
+    @property
+    def times_played(self) -> Optional[int]:
+        ...
+
+    @property
+    def timestamp(self) -> float:
+        ...
+
+class GameStats:
+    def to_dict(self) -> Dict[str, Any]:
+        ...
+
+    @property
+    def requests(self) -> Dict[str, int]:
+        ...
+
+    @property
+    def failures(self) -> Dict[str, int]:
+        ...
+
+    @property
+    def latencies(self) -> Dict[str, List[float]]:
+        ...
+
+    @property
+    def total_requests(self) -> int:
+        ...
+
+    @property
+    def total_latency(self) -> float:
+        ...
+
+    @property
+    def retries(self) -> int:
+        ...
+
+    @property
+    def backs(self) -> int:
+        ...
+
+    @property
+    def progression(self) -> List[Tuple[int, float]]:
+        ...
+
+class Question:
+    def answer(self, answer: Answer) -> None:
+        ...
+
+    def to_dict(self) -> Dict[str, Any]:
+        ...
+
+    @classmethod
+    def from_dict(cls: Type[Question], data: Dict[str, Any]) -> Question:
+        ...
+
+    @property
+    def text(self) -> str:
+        ...
+
+    @property
+    def step(self) -> int:
+        ...
+
+    @property
+    def progression(self) -> float:
+        ...
+
+    @property
+    def id(self) -> Optional[str]:
+        ...
+
+    @property
+    def answers(self) -> List[str]:
+        ...
+
+class MockServer:
+    def __init__(
+        self,
+        *,
+        questions: Optional[List[str]] = None,
+        tree: Optional[Dict[str, Dict[str, str]]] = None,
+        progression: Optional[List[float]] = None,
+        guesses: Optional[List[Dict[str, Any]]] = None,
+        errors: Optional[Dict[int, str]] = None,
+        servers: Optional[List[str]] = None,
+        down_servers: Optional[List[str]] = None,
+        latency: Optional[float] = None,
+    ) -> None:
+        ...
+
+    def inject_error(self, error: str, times: int = 1) -> None:
+        ...
+
+    def expire_sessions(self) -> None:
+        ...
+
+    @property
+    def requests(self) -> List[str]:
+        ...
+
+    @property
+    def sessions(self) -> int:
+        ...
+
+    @property
+    def in_flight_requests(self) -> int:
+        ...
+
+    @property
+    def max_concurrent_requests(self) -> int:
+        ...
+
+class RetryPolicy:
+    def __init__(
+        self,
+        *,
+        max_attempts: Optional[int] = None,
+        base_delay: Optional[float] = None,
+        max_delay: Optional[float] = None,
+        multiplier: Optional[float] = None,
+        jitter: Optional[bool] = None,
+        retry_on: Optional[List[str]] = None,
+    ) -> None:
+        ...
+
+    @property
+    def max_attempts(self) -> int:
+        ...
+
+    @property
+    def base_delay(self) -> float:
+        ...
+
+    @property
+    def max_delay(self) -> float:
+        ...
+
+    @property
+    def multiplier(self) -> float:
+        ...
+
+    @property
+    def jitter(self) -> bool:
+        ...
+
+    @property
+    def retry_on(self) -> List[str]:
+        ...
+
+class GuessPolicy:
+    def __init__(
+        self,
+        *,
+        progression: Optional[float] = None,
+        confidence: Optional[float] = None,
+        max_steps: Optional[int] = None,
+        fallback_step: Optional[int] = None,
+    ) -> None:
+        ...
+
+    @property
+    def progression(self) -> float:
+        ...
+
+    @property
+    def confidence(self) -> float:
+        ...
+
+    @property
+    def max_steps(self) -> Optional[int]:
+        ...
+
+    @property
+    def fallback_step(self) -> Optional[int]:
+        ...
+
+class AnswerResult:
+    @property
+    def kind(self) -> str:
+        ...
+
+    @property
+    def is_guess(self) -> bool:
+        ...
+
+    @property
+    def question(self) -> Optional[Question]:
+        ...
+
+    @property
+    def guess(self) -> Optional[Guess]:
+        ...
+
+class RateLimiter:
+    def __init__(self, rate: float, *, burst: Optional[int] = None) -> None:
+        ...
+
+    @property
+    def rate(self) -> float:
+        ...
+
+    @property
+    def burst(self) -> int:
+        ...
+
 class Akinator:
     def __init__(
         self,
@@ -88,19 +359,74 @@ class Akinator:
         theme: Optional[Theme] = None,
         language: Optional[Language] = None,
         child_mode: Optional[bool] = None,
+        transport: Optional[MockServer] = None,
+        record: Optional[str] = None,
+        replay: Optional[str] = None,
+        proxy: Optional[str] = None,
+        connect_timeout: Optional[float] = None,
+        request_timeout: Optional[float] = None,
+        user_agent: Optional[str] = None,
+        headers: Optional[Dict[str, str]] = None,
+        retry: Optional[RetryPolicy] = None,
+        session_timeout: Optional[float] = None,
+        auto_renew: Optional[bool] = None,
+        rate_limiter: Optional[RateLimiter] = None,
+        guess_policy: Optional[GuessPolicy] = None,
     ) -> None:
         ...
 
-    def start_game(self) -> Optional[str]:
+    def start_game(self) -> Optional[Question]:
+        ...
+
+    def answer(self, answer: Answer, policy: Optional[GuessPolicy] = None) -> Union[Optional[Question], AnswerResult]:
         ...
 
-    def answer(self, answer: Answer) -> Optional[str]:
+    def answer_with_policy(self, answer: Answer, policy: Optional[GuessPolicy] = None) -> AnswerResult:
         ...
 
     def win(self) -> Optional[Guess]:
         ...
 
-    def back(self) -> Optional[str]:
+    def reject_guess(self) -> Optional[Question]:
+        ...
+
+    def continue_game(self) -> Optional[Question]:
+        ...
+
+    def confirm_guess(self, guess: Optional[Guess] = None) -> GameResult:
+        ...
+
+    def back(self) -> Optional[Question]:
+        ...
+
+    def renew(self) -> None:
+        ...
+
+    def close(self) -> None:
+        ...
+
+    def __enter__(self) -> Akinator:
+        ...
+
+    def __exit__(self, *args: Any) -> bool:
+        ...
+
+    def questions(self, threshold: float = 80.0) -> Iterator[Question]:
+        ...
+
+    def to_dict(self) -> Dict[str, Any]:
+        ...
+
+    def to_json(self) -> str:
+        ...
+
+    @classmethod
+    def from_state(
+        cls: Type[Akinator],
+        state: Union[Dict[str, Any], str],
+        *,
+        transport: Optional[MockServer] = None,
+    ) -> Akinator:
         ...
 
     @property
@@ -119,6 +445,10 @@ class Akinator:
     def question(self) -> Optional[str]:
         ...
 
+    @property
+    def current_question(self) -> Optional[Question]:
+        ...
+
     @property
     def progression(self) -> float:
         ...
@@ -135,6 +465,90 @@ class Akinator:
     def guesses(self) -> List[Guess]:
         ...
 
+    @property
+    def state(self) -> GameState:
+        ...
+
+    @property
+    def closed(self) -> bool:
+        ...
+
+    @property
+    def stats(self) -> GameStats:
+        ...
+
+    @property
+    def rejected_guesses(self) -> List[Guess]:
+        ...
+
+    @property
+    def finished(self) -> bool:
+        ...
+
+    @property
+    def result(self) -> Optional[GameResult]:
+        ...
+
+    @property
+    def history(self) -> List[HistoryEntry]:
+        ...
+
+    @property
+    def server(self) -> Optional[str]:
+        ...
+
+    @property
+    def proxy(self) -> Optional[str]:
+        ...
+
+    @property
+    def connect_timeout(self) -> Optional[float]:
+        ...
+
+    @property
+    def request_timeout(self) -> Optional[float]:
+        ...
+
+    @property
+    def user_agent(self) -> Optional[str]:
+        ...
+
+    @property
+    def headers(self) -> Dict[str, str]:
+        ...
+
+    @property
+    def retry(self) -> Optional[RetryPolicy]:
+        ...
+
+    @property
+    def transport(self) -> Optional[MockServer]:
+        ...
+
+    @property
+    def rate_limiter(self) -> Optional[RateLimiter]:
+        ...
+
+    @property
+    def guess_policy(self) -> Optional[GuessPolicy]:
+        ...
+
+    @property
+    def session_timeout(self) -> float:
+        ...
+
+    @property
+    def auto_renew(self) -> bool:
+        ...
+
+    @property
+    def idle_seconds(self) -> Optional[float]:
+        ...
+
+    @property
+    def is_expired(self) -> bool:
+        ...
+
     @theme.setter
     def set_theme(self, theme: Theme) -> None:
         ...
@@ -147,6 +561,50 @@ class Akinator:
     def set_child_mode(self, child_mode: bool) -> None:
         ...
 
+    @proxy.setter
+    def set_proxy(self, proxy: Optional[str]) -> None:
+        ...
+
+    @connect_timeout.setter
+    def set_connect_timeout(self, connect_timeout: Optional[float]) -> None:
+        ...
+
+    @request_timeout.setter
+    def set_request_timeout(self, request_timeout: Optional[float]) -> None:
+        ...
+
+    @user_agent.setter
+    def set_user_agent(self, user_agent: Optional[str]) -> None:
+        ...
+
+    @headers.setter
+    def set_headers(self, headers: Dict[str, str]) -> None:
+        ...
+
+    @retry.setter
+    def set_retry(self, retry: Optional[RetryPolicy]) -> None:
+        ...
+
+    @transport.setter
+    def set_transport(self, transport: Optional[MockServer]) -> None:
+        ...
+
+    @rate_limiter.setter
+    def set_rate_limiter(self, rate_limiter: Optional[RateLimiter]) -> None:
+        ...
+
+    @guess_policy.setter
+    def set_guess_policy(self, guess_policy: Optional[GuessPolicy]) -> None:
+        ...
+
+    @session_timeout.setter
+    def set_session_timeout(self, session_timeout: float) -> None:
+        ...
+
+    @auto_renew.setter
+    def set_auto_renew(self, auto_renew: bool) -> None:
+        ...
+
 class AsyncAkinator:
     def __init__(
         self,
@@ -154,19 +612,74 @@ class AsyncAkinator:
         theme: Optional[Theme] = None,
         language: Optional[Language] = None,
         child_mode: Optional[bool] = None,
+        transport: Optional[MockServer] = None,
+        record: Optional[str] = None,
+        replay: Optional[str] = None,
+        proxy: Optional[str] = None,
+        connect_timeout: Optional[float] = None,
+        request_timeout: Optional[float] = None,
+        user_agent: Optional[str] = None,
+        headers: Optional[Dict[str, str]] = None,
+        retry: Optional[RetryPolicy] = None,
+        session_timeout: Optional[float] = None,
+        auto_renew: Optional[bool] = None,
+        rate_limiter: Optional[RateLimiter] = None,
+        guess_policy: Optional[GuessPolicy] = None,
     ) -> None:
         ...
 
-    async def start_game(self) -> Optional[str]:
+    async def start_game(self) -> Optional[Question]:
+        ...
+
+    async def answer(self, answer: Answer, policy: Optional[GuessPolicy] = None) -> Union[Optional[Question], AnswerResult]:
         ...
 
-    async def answer(self, answer: Answer) -> Optional[str]:
+    async def answer_with_policy(self, answer: Answer, policy: Optional[GuessPolicy] = None) -> AnswerResult:
         ...
 
     async def win(self) -> Optional[Guess]:
         ...
 
-    async def back(self) -> Optional[str]:
+    async def reject_guess(self) -> Optional[Question]:
+        ...
+
+    async def continue_game(self) -> Optional[Question]:
+        ...
+
+    async def confirm_guess(self, guess: Optional[Guess] = None) -> GameResult:
+        ...
+
+    async def back(self) -> Optional[Question]:
+        ...
+
+    async def renew(self) -> None:
+        ...
+
+    async def close(self) -> None:
+        ...
+
+    async def __aenter__(self) -> AsyncAkinator:
+        ...
+
+    async def __aexit__(self, *args: Any) -> bool:
+        ...
+
+    def questions(self, threshold: float = 80.0) -> AsyncIterator[Question]:
+        ...
+
+    def to_dict(self) -> Dict[str, Any]:
+        ...
+
+    def to_json(self) -> str:
+        ...
+
+    @classmethod
+    def from_state(
+        cls: Type[AsyncAkinator],
+        state: Union[Dict[str, Any], str],
+        *,
+        transport: Optional[MockServer] = None,
+    ) -> AsyncAkinator:
         ...
 
     @property
@@ -185,6 +698,10 @@ class AsyncAkinator:
     def question(self) -> Optional[str]:
         ...
 
+    @property
+    def current_question(self) -> Optional[Question]:
+        ...
+
     @property
     def progression(self) -> float:
         ...
@@ -201,6 +718,90 @@ class AsyncAkinator:
     def guesses(self) -> List[Guess]:
         ...
 
+    @property
+    def state(self) -> GameState:
+        ...
+
+    @property
+    def closed(self) -> bool:
+        ...
+
+    @property
+    def stats(self) -> GameStats:
+        ...
+
+    @property
+    def rejected_guesses(self) -> List[Guess]:
+        ...
+
+    @property
+    def finished(self) -> bool:
+        ...
+
+    @property
+    def result(self) -> Optional[GameResult]:
+        ...
+
+    @property
+    def history(self) -> List[HistoryEntry]:
+        ...
+
+    @property
+    def server(self) -> Optional[str]:
+        ...
+
+    @property
+    def proxy(self) -> Optional[str]:
+        ...
+
+    @property
+    def connect_timeout(self) -> Optional[float]:
+        ...
+
+    @property
+    def request_timeout(self) -> Optional[float]:
+        ...
+
+    @property
+    def user_agent(self) -> Optional[str]:
+        ...
+
+    @property
+    def headers(self) -> Dict[str, str]:
+        ...
+
+    @property
+    def retry(self) -> Optional[RetryPolicy]:
+        ...
+
+    @property
+    def transport(self) -> Optional[MockServer]:
+        ...
+
+    @property
+    def rate_limiter(self) -> Optional[RateLimiter]:
+        ...
+
+    @property
+    def guess_policy(self) -> Optional[GuessPolicy]:
+        ...
+
+    @property
+    def session_timeout(self) -> float:
+        ...
+
+    @property
+    def auto_renew(self) -> bool:
+        ...
+
+    @property
+    def idle_seconds(self) -> Optional[float]:
+        ...
+
+    @property
+    def is_expired(self) -> bool:
+        ...
+
     @theme.setter
     def set_theme(self, theme: Theme) -> None:
         ...
@@ -213,26 +814,210 @@ class AsyncAkinator:
     def set_child_mode(self, child_mode: bool) -> None:
         ...
 
-class CantGoBackAnyFurther(Exception):
+    @proxy.setter
+    def set_proxy(self, proxy: Optional[str]) -> None:
+        ...
+
+    @connect_timeout.setter
+    def set_connect_timeout(self, connect_timeout: Optional[float]) -> None:
+        ...
+
+    @request_timeout.setter
+    def set_request_timeout(self, request_timeout: Optional[float]) -> None:
+        ...
+
+    @user_agent.setter
+    def set_user_agent(self, user_agent: Optional[str]) -> None:
+        ...
+
+    @headers.setter
+    def set_headers(self, headers: Dict[str, str]) -> None:
+        ...
+
+    @retry.setter
+    def set_retry(self, retry: Optional[RetryPolicy]) -> None:
+        ...
+
+    @transport.setter
+    def set_transport(self, transport: Optional[MockServer]) -> None:
+        ...
+
+    @rate_limiter.setter
+    def set_rate_limiter(self, rate_limiter: Optional[RateLimiter]) -> None:
+        ...
+
+    @guess_policy.setter
+    def set_guess_policy(self, guess_policy: Optional[GuessPolicy]) -> None:
+        ...
+
+    @session_timeout.setter
+    def set_session_timeout(self, session_timeout: float) -> None:
+        ...
+
+    @auto_renew.setter
+    def set_auto_renew(self, auto_renew: bool) -> None:
+        ...
+
+class AkinatorPool:
+    def __init__(
+        self,
+        *,
+        ttl: Optional[float] = None,
+        max_sessions: Optional[int] = None,
+        theme: Optional[Theme] = None,
+        language: Optional[Language] = None,
+        child_mode: Optional[bool] = None,
+        transport: Optional[MockServer] = None,
+        proxy: Optional[str] = None,
+        connect_timeout: Optional[float] = None,
+        request_timeout: Optional[float] = None,
+        user_agent: Optional[str] = None,
+        headers: Optional[Dict[str, str]] = None,
+        retry: Optional[RetryPolicy] = None,
+        rate_limiter: Optional[RateLimiter] = None,
+    ) -> None:
+        ...
+
+    def __len__(self) -> int:
+        ...
+
+    def __contains__(self, key: Union[int, str]) -> bool:
+        ...
+
+    def get(self, key: Union[int, str]) -> Optional[AsyncAkinator]:
+        ...
+
+    def create(
+        self,
+        key: Union[int, str],
+        *,
+        theme: Optional[Theme] = None,
+        language: Optional[Language] = None,
+        child_mode: Optional[bool] = None,
+    ) -> AsyncAkinator:
+        ...
+
+    def get_or_create(
+        self,
+        key: Union[int, str],
+        *,
+        theme: Optional[Theme] = None,
+        language: Optional[Language] = None,
+        child_mode: Optional[bool] = None,
+    ) -> AsyncAkinator:
+        ...
+
+    def remove(self, key: Union[int, str]) -> bool:
+        ...
+
+    def evict_expired(self) -> int:
+        ...
+
+    def keys(self) -> List[Union[int, str]]:
+        ...
+
+    def close(self) -> None:
+        ...
+
+    @property
+    def active_sessions(self) -> int:
+        ...
+
+    @property
+    def ttl(self) -> Optional[float]:
+        ...
+
+    @property
+    def max_sessions(self) -> Optional[int]:
+        ...
+
+    @property
+    def rate_limiter(self) -> Optional[RateLimiter]:
+        ...
+
+class AkinatorError(Exception):
+    step: Optional[int]
+    question: Optional[str]
+    status: Optional[int]
+    snippet: Optional[str]
+
+class CantGoBackAnyFurther(AkinatorError):
+    ...
+
+class InvalidAnswer(AkinatorError, ValueError):
+    ...
+
+class InvalidLanguage(AkinatorError, ValueError):
+    ...
+
+class ConnectionError(AkinatorError, builtins.ConnectionError):
+    ...
+
+class NoMoreQuestions(AkinatorError):
+    ...
+
+class TimeoutError(AkinatorError, builtins.TimeoutError):
+    ...
+
+class InvalidGameState(AkinatorError, RuntimeError):
+    current: Optional[GameState]
+    required: Optional[List[GameState]]
+
+class TechnicalError(AkinatorError):
+    ...
+
+class ServersDown(AkinatorError):
+    ...
+
+class SessionClosed(AkinatorError):
+    ...
+
+class GameFinished(AkinatorError):
     ...
 
-class InvalidAnswer(Exception):
+class SessionDiverged(AkinatorError):
     ...
 
-class InvalidLanguage(Exception):
+class RequestError(AkinatorError):
     ...
 
-class ConnectionError(Exception):
+class JsonParseError(AkinatorError):
     ...
 
-class NoMoreQuestions(Exception):
+class NoDataFound(AkinatorError):
     ...
 
-class TimeoutError(Exception):
+class UpdateInfoError(AkinatorError):
     ...
 
-class TechnicalError(Exception):
+class TimeError(AkinatorError):
     ...
 
-class ServersDown(Exception):
-    ...
\ No newline at end of file
+class MissingData(AkinatorError, RuntimeError):
+    ...
+
+class CassetteError(AkinatorError, RuntimeError):
+    ...
+
+class ConfigError(AkinatorError, ValueError):
+    ...
+
+class UnansweredQuestion(AkinatorError, RuntimeError):
+    ...
+
+class PoisonError(AkinatorError, RuntimeError):
+    ...
+
+def configure_runtime(
+    *,
+    worker_threads: Optional[int] = None,
+    thread_name: Optional[str] = None,
+    enable_io_driver: Optional[bool] = None,
+) -> None:
+    ...
+
+def set_rate_limiter(limiter: Optional[RateLimiter]) -> None:
+    ...
+
+def get_rate_limiter() -> Optional[RateLimiter]:
+    ...
@@ -6,6 +6,10 @@ use pyo3::{
 };
 
 use crate::error::Error;
+use serde::{
+    Serialize,
+    Deserialize,
+};
 use akinator_rs::enums::{
     Theme as ThemeEnum,
     Answer as AnswerEnum,
@@ -26,8 +30,8 @@ macro_rules! cast_enum {
 /// An enum class representing an answer given to the akinator
 ///
 /// This is meant for the user to use to pass into methods such as `Akinator.answer`
-#[pyclass]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[pyclass(module = "akinator")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Answer {
     Yes = 0,
     No = 1,
@@ -39,8 +43,8 @@ pub enum Answer {
 /// An enum class representing the theme of an akinator game
 ///
 /// This is meant for the user to use to pass into the Akinator constructor, or to set the theme property
-#[pyclass]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[pyclass(module = "akinator")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Theme {
     Characters = 1,
     Animals = 14,
@@ -50,8 +54,8 @@ pub enum Theme {
 /// An enum class representing the language of the akinator game
 ///
 /// This is meant for the user to use to pass into the Akinator constructor, or to set the language property
-#[pyclass]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[pyclass(module = "akinator")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Language {
     English,
     Arabic,
@@ -71,6 +75,29 @@ pub enum Language {
     Indonesian,
 }
 
+/// An enum class representing the state an akinator game is in
+///
+/// This is returned by the ``state`` property of the :class:`Akinator` class, and decides which of its methods can be called
+#[pyclass(module = "akinator")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameState {
+    /// ``start_game`` has not been called yet
+    NotStarted,
+    /// questions are being asked, until ``win`` is called
+    InProgress,
+    /// the akinator made its guess, waiting for it to be confirmed or rejected
+    Guessing,
+    /// the akinator's guess was confirmed with ``confirm_guess``
+    Won,
+    /// the akinator ran out of questions to ask, or of guesses to make
+    Lost,
+    /// the session has timed out, or has gone idle for longer than the session timeout and has likely expired,
+    /// only refusing requests once the akinator servers have actually timed it out, until it is renewed
+    Expired,
+    /// the game has been closed with ``close``
+    Closed,
+}
+
 #[pymethods]
 impl Answer {
     /// a classmethod to return an :class:`Answer` enum variant parsing from a :class:`str`
@@ -107,6 +134,13 @@ impl Answer {
     fn __str__(&self) -> String {
         format!("{self:?}")
     }
+
+    fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (&'a PyType, String))> {
+        let getattr = py.import("builtins")?
+            .getattr("getattr")?;
+
+        Ok((getattr, (py.get_type::<Self>(), format!("{self:?}"))))
+    }
 }
 
 #[pymethods]
@@ -135,6 +169,13 @@ impl Theme {
     fn __str__(&self) -> String {
         format!("{self:?}")
     }
+
+    fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (&'a PyType, String))> {
+        let getattr = py.import("builtins")?
+            .getattr("getattr")?;
+
+        Ok((getattr, (py.get_type::<Self>(), format!("{self:?}"))))
+    }
 }
 
 #[pymethods]
@@ -168,6 +209,55 @@ impl Language {
     fn __str__(&self) -> String {
         format!("{self:?}")
     }
+
+    fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (&'a PyType, String))> {
+        let getattr = py.import("builtins")?
+            .getattr("getattr")?;
+
+        Ok((getattr, (py.get_type::<Self>(), format!("{self:?}"))))
+    }
+}
+
+#[pymethods]
+impl GameState {
+    fn __repr__(&self) -> String {
+        format!("<GameState state=\"{self:?}\">")
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (&'a PyType, String))> {
+        let getattr = py.import("builtins")?
+            .getattr("getattr")?;
+
+        Ok((getattr, (py.get_type::<Self>(), format!("{self:?}"))))
+    }
+}
+
+impl Language {
+    /// the subdomain of the akinator website serving this language
+    pub(crate) const fn code(&self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Arabic => "ar",
+            Self::Chinese => "cn",
+            Self::German => "de",
+            Self::Spanish => "es",
+            Self::French => "fr",
+            Self::Hebrew => "il",
+            Self::Italian => "it",
+            Self::Japanese => "jp",
+            Self::Korean => "kr",
+            Self::Dutch => "nl",
+            Self::Polish => "pl",
+            Self::Portugese => "pt",
+            Self::Russian => "ru",
+            Self::Turkish => "tr",
+            Self::Indonesian => "id",
+        }
+    }
 }
 
 impl From<AnswerEnum> for Answer {
@@ -276,4 +366,4 @@ impl From<Language> for LanguageEnum {
             Indonesian,
         )
     }
-}
\ No newline at end of file
+}
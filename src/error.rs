@@ -1,56 +1,329 @@
 use pyo3::{
     prelude::*,
     create_exception,
+    once_cell::GILOnceCell,
+    types::{
+        PyDict,
+        PyType,
+    },
     exceptions::{
         PyException,
+        PyOSError,
         PyValueError,
         PyRuntimeError,
-    }
+        PyTimeoutError,
+        PyConnectionError,
+    },
+    PyErrArguments,
 };
 
-use std::sync::PoisonError;
+use crate::enums::GameState;
+
+use std::{
+    io,
+    sync,
+    time::SystemTimeError,
+};
 use akinator_rs::error::Error as AkiError;
 
 
+/// the number of characters of a response body kept in the context of an error
+const SNIPPET_LENGTH: usize = 200;
+
+/// the attributes every exception raised by the library carries, ``None`` unless known
+const CONTEXT_ATTRIBUTES: [&str; 4] = ["step", "question", "status", "snippet"];
+
+create_exception!(
+    akinator,
+    AkinatorError,
+    PyException,
+    "The base class of every exception raised by the akinator, carrying the ``step`` and ``question`` the game was on, \
+    and the HTTP ``status`` and a ``snippet`` of the body of the response that caused it, where known"
+);
+
 macro_rules! create_exceptions {
     ( $(( $name:ident, $doc:expr )),* $(,)* ) => {
         $(
             create_exception!(
                 akinator,
                 $name,
-                PyException,
+                AkinatorError,
                 $doc
             );
         )*
     }
 }
 
+/// creates exceptions that subclass both ``AkinatorError`` and a python builtin exception,
+/// so that they are also caught by generic handlers of the builtin
+///
+/// ``create_exception!`` only supports a single base class, so these are created by calling ``type`` from python instead
+macro_rules! create_builtin_exceptions {
+    ( $(( $name:ident, $builtin:ty, $doc:expr )),* $(,)* ) => {
+        $(
+            #[doc = $doc]
+            pub struct $name;
+
+            impl $name {
+                /// the python type of the exception, created on first use
+                pub fn type_object(py: Python<'_>) -> &PyType {
+                    static TYPE_OBJECT: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+                    TYPE_OBJECT
+                        .get_or_init(py, || {
+                            new_builtin_subclass(py, stringify!($name), $doc, py.get_type::<$builtin>())
+                                .expect(concat!("Failed to create the ", stringify!($name), " exception type"))
+                        })
+                        .as_ref(py)
+                }
+
+                /// creates a new instance of the exception, raised with ``args``
+                pub fn new_err<A>(args: A) -> PyErr
+                where
+                    A: PyErrArguments + Send + Sync + 'static,
+                {
+                    Python::with_gil(|py| PyErr::from_type(Self::type_object(py), args))
+                }
+            }
+        )*
+    }
+}
+
+/// creates a python exception type named ``name`` subclassing both ``AkinatorError`` and ``builtin``
+fn new_builtin_subclass(py: Python<'_>, name: &str, doc: &str, builtin: &PyType) -> PyResult<Py<PyType>> {
+    let namespace = PyDict::new(py);
+    namespace.set_item("__module__", "akinator")?;
+    namespace.set_item("__doc__", doc)?;
+
+    let bases = (py.get_type::<AkinatorError>(), builtin);
+
+    Ok(py.get_type::<PyType>()
+        .call1((name, bases, namespace))?
+        .downcast::<PyType>()?
+        .into())
+}
+
+create_builtin_exceptions![
+    (InvalidAnswer, PyValueError, "Raised when an invalid answer string is used when instantiating a Language enum from str"),
+    (InvalidLanguage, PyValueError, "Raised when an invalid language string is used when instantiating a Language enum from str"),
+    (ConnectionError, PyConnectionError, "Raised when we fail the connect to the akinator servers for whatever reason"),
+    (TimeoutError, PyTimeoutError, "Raised when the akinator session timed out waiting for a response"),
+    (
+        InvalidGameState,
+        PyRuntimeError,
+        "Raised when calling a method the game is not in the right state for, such as ``answer`` before ``start_game``, \
+        carrying the ``current`` state of the game and the ``required`` states the method can be called in"
+    ),
+    (MissingData, PyRuntimeError, "Raised when the data required to make a request is missing, such as when the game has not been started"),
+    (CassetteError, PyRuntimeError, "Raised when a recorded cassette cannot be replayed, or has no response left for a request"),
+    (ConfigError, PyValueError, "Raised when the configuration of a game, such as its HTTP client, is invalid"),
+    (UnansweredQuestion, PyRuntimeError, "Raised when moving on to the next question of ``questions()`` before answering the current one"),
+    (PoisonError, PyRuntimeError, "Raised when the state of a game could not be read, as a thread panicked while updating it"),
+];
+
 create_exceptions![
     (CantGoBackAnyFurther, "Raised when the akinator is already on the 1st question / there are no more questions to go back on"),
-    (InvalidAnswer, "Raised when an invalid answer string is used when instantiating a Language enum from str"),
-    (InvalidLanguage, "Raised when an invalid language string is used when instantiating a Language enum from str"),
-    (ConnectionError, "Raised when we fail the connect to the akinator servers for whatever reason"),
     (NoMoreQuestions, "Raised when there are no more questions the akinator can offer"),
-    (TimeoutError, "Raised when the akinator session timed out waiting for a response"),
     (TechnicalError, "Raised when there is a technical internal error with the akinator servers"),
     (ServersDown, "Raised when the akinator servers in the requested region are down"),
+    (SessionClosed, "Raised when trying to use an akinator game that has already been closed"),
+    (GameFinished, "Raised when trying to keep playing an akinator game whose guess has already been confirmed"),
+    (
+        SessionDiverged,
+        "Raised when renewing an expired session fails as the akinator asked a different question than the one originally answered, \
+        carrying the ``step`` it diverged at"
+    ),
+    (RequestError, "Raised when an HTTP request to the akinator servers fails"),
+    (JsonParseError, "Raised when a response of the akinator servers could not be parsed"),
+    (NoDataFound, "Raised when the data required to continue could not be found in a response of the akinator servers"),
+    (UpdateInfoError, "Raised when a field of a response of the akinator servers is missing or invalid"),
+    (TimeError, "Raised when the current timestamp could not be determined"),
 ];
 
+/// details about where an error occured, exposed as attributes of the python exception it is raised as
+#[derive(Debug, Clone, Default)]
+pub struct ErrorContext {
+    pub step: Option<usize>,
+    pub question: Option<String>,
+    pub status: Option<u16>,
+    pub snippet: Option<String>,
+}
+
+impl ErrorContext {
+    /// the context of an error caused by a response with the given ``status`` and ``body``
+    pub fn response(status: Option<u16>, body: &str) -> Self {
+        Self {
+            status,
+            snippet: Some(body.chars().take(SNIPPET_LENGTH).collect()),
+            ..Self::default()
+        }
+    }
+
+    /// fills in the details missing from ``self`` with those of ``other``
+    fn or(self, other: Self) -> Self {
+        Self {
+            step: self.step.or(other.step),
+            question: self.question.or(other.question),
+            status: self.status.or(other.status),
+            snippet: self.snippet.or(other.snippet),
+        }
+    }
+
+    fn attach(self, py: Python<'_>, err: &PyErr) -> PyResult<()> {
+        let value = err.value(py);
+
+        value.setattr("step", self.step)?;
+        value.setattr("question", self.question)?;
+        value.setattr("status", self.status)?;
+        value.setattr("snippet", self.snippet)?;
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     AkiError(AkiError),
+    RequestError(reqwest::Error),
+    JsonParseError(serde_json::Error),
+    TimeError(SystemTimeError),
+    UpdateInfoError(String),
+    MissingData,
+    IoError(io::Error),
+    CassetteError(String),
+    ConfigError(String),
+    UnansweredQuestion,
+    InvalidGameState(GameState, Vec<GameState>),
+    SessionClosed,
+    GameFinished,
+    SessionDiverged(usize),
     PoisonError,
+    /// an error read back from a cassette, raised as the exception of its recorded ``kind``
+    Replayed {
+        kind: String,
+        message: String,
+        unprocessed: bool,
+    },
+    Context(Box<Error>, ErrorContext),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// attaches ``context`` to this error, keeping any details it already had
+    #[must_use]
+    pub fn with_context(self, context: ErrorContext) -> Self {
+        match self {
+            Self::Context(error, existing) => Self::Context(error, existing.or(context)),
+            error => Self::Context(Box::new(error), context),
+        }
+    }
+
+    /// the underlying error, without any context attached to it
+    pub fn inner(&self) -> &Self {
+        match self {
+            Self::Context(error, _) => error.inner(),
+            error => error,
+        }
+    }
+
+    /// the details attached to this error, if any
+    pub const fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Context(_, context) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// the name of the kind of this error, matching the python exception names where there is one
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::AkiError(err) => match err {
+                AkiError::CantGoBackAnyFurther => "CantGoBackAnyFurther",
+                AkiError::InvalidAnswer => "InvalidAnswer",
+                AkiError::InvalidLanguage => "InvalidLanguage",
+                AkiError::ConnectionError => "ConnectionError",
+                AkiError::NoMoreQuestions => "NoMoreQuestions",
+                AkiError::TimeoutError => "TimeoutError",
+                AkiError::TechnicalError => "TechnicalError",
+                AkiError::ServersDown => "ServersDown",
+                AkiError::NoDataFound => "NoDataFound",
+                AkiError::UpdateInfoError(_) => "UpdateInfoError",
+                AkiError::TimeError(_) => "TimeError",
+                AkiError::RequestError(_) => "RequestError",
+                AkiError::JsonParseError(_) => "JsonParseError",
+            },
+            Self::RequestError(_) => "RequestError",
+            Self::JsonParseError(_) => "JsonParseError",
+            Self::TimeError(_) => "TimeError",
+            Self::UpdateInfoError(_) => "UpdateInfoError",
+            Self::MissingData => "MissingData",
+            Self::IoError(_) => "IoError",
+            Self::CassetteError(_) => "CassetteError",
+            Self::ConfigError(_) => "ConfigError",
+            Self::UnansweredQuestion => "UnansweredQuestion",
+            Self::InvalidGameState(..) => "InvalidGameState",
+            Self::SessionClosed => "SessionClosed",
+            Self::GameFinished => "GameFinished",
+            Self::SessionDiverged(_) => "SessionDiverged",
+            Self::PoisonError => "PoisonError",
+            Self::Replayed { kind, .. } => kind,
+            Self::Context(error, _) => error.kind(),
+        }
+    }
+
+    /// whether the server can be sure to not have acted upon the request that failed with this error,
+    /// making it safe to send again even if it is not idempotent
+    pub fn is_unprocessed(&self) -> bool {
+        match self {
+            Self::AkiError(err) => matches!(
+                err,
+                AkiError::ConnectionError | AkiError::TechnicalError | AkiError::ServersDown
+            ),
+            Self::RequestError(err) => err.is_connect(),
+            Self::Replayed { unprocessed, .. } => *unprocessed,
+            Self::Context(error, _) => error.is_unprocessed(),
+            _ => false,
+        }
+    }
 }
 
 pub(crate) fn add_exceptions(py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    let base = py.get_type::<AkinatorError>();
+
+    for attribute in CONTEXT_ATTRIBUTES {
+        base.setattr(attribute, py.None())?;
+    }
+
+    let invalid_state = InvalidGameState::type_object(py);
+
+    for attribute in ["current", "required"] {
+        invalid_state.setattr(attribute, py.None())?;
+    }
+
+    module.add("AkinatorError", base)?;
     module.add("CantGoBackAnyFurther", py.get_type::<CantGoBackAnyFurther>())?;
-    module.add("InvalidAnswer", py.get_type::<InvalidAnswer>())?;
-    module.add("InvalidLanguage", py.get_type::<InvalidLanguage>())?;
-    module.add("ConnectionError", py.get_type::<ConnectionError>())?;
+    module.add("InvalidAnswer", InvalidAnswer::type_object(py))?;
+    module.add("InvalidLanguage", InvalidLanguage::type_object(py))?;
+    module.add("ConnectionError", ConnectionError::type_object(py))?;
     module.add("NoMoreQuestions", py.get_type::<NoMoreQuestions>())?;
-    module.add("TimeoutError", py.get_type::<TimeoutError>())?;
+    module.add("TimeoutError", TimeoutError::type_object(py))?;
+    module.add("InvalidGameState", invalid_state)?;
     module.add("TechnicalError", py.get_type::<TechnicalError>())?;
     module.add("ServersDown", py.get_type::<ServersDown>())?;
+    module.add("SessionClosed", py.get_type::<SessionClosed>())?;
+    module.add("GameFinished", py.get_type::<GameFinished>())?;
+    module.add("SessionDiverged", py.get_type::<SessionDiverged>())?;
+    module.add("RequestError", py.get_type::<RequestError>())?;
+    module.add("JsonParseError", py.get_type::<JsonParseError>())?;
+    module.add("NoDataFound", py.get_type::<NoDataFound>())?;
+    module.add("UpdateInfoError", py.get_type::<UpdateInfoError>())?;
+    module.add("TimeError", py.get_type::<TimeError>())?;
+    module.add("MissingData", MissingData::type_object(py))?;
+    module.add("CassetteError", CassetteError::type_object(py))?;
+    module.add("ConfigError", ConfigError::type_object(py))?;
+    module.add("UnansweredQuestion", UnansweredQuestion::type_object(py))?;
+    module.add("PoisonError", PoisonError::type_object(py))?;
 
     Ok(())
 }
@@ -75,20 +348,124 @@ impl From<Error> for PyErr {
                     TechnicalError::new_err("An unknown technical error occured within the akinator servers"),
                 AkiError::ServersDown =>
                     ServersDown::new_err("The requested akinator servers are down"),
-                AkiError::NoDataFound  | AkiError::UpdateInfoError(_) |
-                AkiError::TimeError(_) | AkiError::RequestError(_) =>
-                    PyRuntimeError::new_err(err.to_string()),
+                AkiError::NoDataFound =>
+                    NoDataFound::new_err(err.to_string()),
+                AkiError::UpdateInfoError(_) =>
+                    UpdateInfoError::new_err(err.to_string()),
+                AkiError::TimeError(_) =>
+                    TimeError::new_err(err.to_string()),
+                AkiError::RequestError(_) =>
+                    RequestError::new_err(err.to_string()),
                 AkiError::JsonParseError(_) =>
-                    PyValueError::new_err(err.to_string()),
+                    JsonParseError::new_err(err.to_string()),
+            },
+            Error::RequestError(err) =>
+                RequestError::new_err(format!("Request error: {err}")),
+            Error::JsonParseError(err) =>
+                JsonParseError::new_err(format!("Failed to parse the JSON response: {err}")),
+            Error::TimeError(err) =>
+                TimeError::new_err(format!("Failed to get the current timestamp: {err}")),
+            Error::UpdateInfoError(field) =>
+                UpdateInfoError::new_err(format!("Failed to update the game info, `{field}` is missing or invalid")),
+            Error::MissingData =>
+                MissingData::new_err("Missing required data to continue, has the game been started?"),
+            Error::IoError(err) =>
+                err.into(),
+            Error::CassetteError(reason) =>
+                CassetteError::new_err(format!("Failed to replay the cassette: {reason}")),
+            Error::ConfigError(reason) =>
+                ConfigError::new_err(format!("Invalid configuration: {reason}")),
+            Error::UnansweredQuestion =>
+                UnansweredQuestion::new_err("The current question must be answered before moving on to the next one"),
+            Error::InvalidGameState(current, required) => {
+                let expected = required
+                    .iter()
+                    .map(|state| format!("`{state:?}`"))
+                    .collect::<Vec<_>>()
+                    .join(" or ");
+                let err = InvalidGameState::new_err(format!(
+                    "The akinator game is `{current:?}`, but has to be {expected} to do this"
+                ));
+
+                Python::with_gil(|py| {
+                    let attached = (|| {
+                        let value = err.value(py);
+
+                        value.setattr("current", current.into_py(py))?;
+                        value.setattr("required", required.into_py(py))
+                    })();
+
+                    if let Err(attach_err) = attached {
+                        attach_err.print(py);
+                    }
+                });
+
+                err
+            },
+            Error::SessionClosed =>
+                SessionClosed::new_err("The akinator game has been closed"),
+            Error::GameFinished =>
+                GameFinished::new_err("The akinator game is over, its guess has already been confirmed"),
+            Error::SessionDiverged(step) => {
+                let err = SessionDiverged::new_err(format!(
+                    "Failed to renew the expired akinator session, a different question was asked at step {step}"
+                ));
+
+                Python::with_gil(|py| {
+                    if let Err(attach_err) = err.value(py).setattr("step", step) {
+                        attach_err.print(py);
+                    }
+                });
+
+                err
             },
             Error::PoisonError =>
-                PyRuntimeError::new_err("Failed to read attribute values"),
+                PoisonError::new_err("Failed to read attribute values"),
+            Error::Replayed { kind, message, .. } =>
+                replayed_exception(&kind, format!("Replayed a recorded {kind}: {message}")),
+            Error::Context(error, context) => {
+                let err = Self::from(*error);
+
+                Python::with_gil(|py| {
+                    // the exception is still worth raising without its context
+                    if let Err(attach_err) = context.attach(py, &err) {
+                        attach_err.print(py);
+                    }
+                });
+
+                err
+            },
         }
     }
 }
 
-impl<T> From<PoisonError<T>> for Error {
-    fn from(_error: PoisonError<T>) -> Self {
+/// the exception named ``kind`` raised with ``message``,
+/// falling back to ``RequestError`` for the kinds of errors that can only be raised by making a request
+fn replayed_exception(kind: &str, message: String) -> PyErr {
+    match kind {
+        "CantGoBackAnyFurther" => CantGoBackAnyFurther::new_err(message),
+        "InvalidAnswer" => InvalidAnswer::new_err(message),
+        "InvalidLanguage" => InvalidLanguage::new_err(message),
+        "ConnectionError" => ConnectionError::new_err(message),
+        "NoMoreQuestions" => NoMoreQuestions::new_err(message),
+        "TimeoutError" => TimeoutError::new_err(message),
+        "TechnicalError" => TechnicalError::new_err(message),
+        "ServersDown" => ServersDown::new_err(message),
+        "NoDataFound" => NoDataFound::new_err(message),
+        "UpdateInfoError" => UpdateInfoError::new_err(message),
+        "TimeError" => TimeError::new_err(message),
+        "JsonParseError" => JsonParseError::new_err(message),
+        "MissingData" => MissingData::new_err(message),
+        "IoError" => PyOSError::new_err(message),
+        "CassetteError" => CassetteError::new_err(message),
+        "ConfigError" => ConfigError::new_err(message),
+        "SessionClosed" => SessionClosed::new_err(message),
+        _ => RequestError::new_err(message),
+    }
+}
+
+impl<T> From<sync::PoisonError<T>> for Error {
+    fn from(_error: sync::PoisonError<T>) -> Self {
         Self::PoisonError
     }
 }
@@ -97,4 +474,28 @@ impl From<AkiError> for Error {
     fn from(error: AkiError) -> Self {
         Self::AkiError(error)
     }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Self::RequestError(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::JsonParseError(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+
+impl From<SystemTimeError> for Error {
+    fn from(error: SystemTimeError) -> Self {
+        Self::TimeError(error)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,1296 @@
+//! The akinator game client shared by the blocking and async python classes
+//!
+//! this talks to the akinator API directly (rather than through ``akinator_rs::Akinator``)
+//! so that the full session state is available to be saved and restored
+
+use crate::{
+    enums::{
+        Theme,
+        Answer,
+        Language,
+        GameState,
+    },
+    error::{
+        Error,
+        ErrorContext,
+        Result,
+    },
+    logging::{
+        log,
+        Level,
+    },
+    guess_policy::GuessPolicy,
+    mock::MockServer,
+    models::{
+        AnswerOutcome,
+        GuessModel,
+        GameResultModel,
+        HistoryEntryModel,
+        QuestionModel,
+    },
+    rate_limit::RateLimiter,
+    retry::RetryPolicy,
+    stats::StatsModel,
+    transport::{
+        Transport,
+        HttpConfig,
+        HttpTransport,
+        RecordingTransport,
+        timeout_from_secs,
+    },
+};
+
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use akinator_rs::error::Error as AkiError;
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+    Deserialize,
+};
+
+/// the common envelope every akinator API response is wrapped in
+#[derive(Deserialize)]
+struct Response<T> {
+    completion: String,
+    parameters: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct StartParameters {
+    identification: Identification,
+    step_information: StepInformation,
+}
+
+#[derive(Deserialize)]
+struct Identification {
+    session: String,
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct StepInformation {
+    question: String,
+    step: String,
+    progression: String,
+    #[serde(default, rename = "questionid")]
+    question_id: Option<String>,
+    #[serde(default)]
+    answers: Vec<AnswerLabel>,
+}
+
+#[derive(Deserialize)]
+struct AnswerLabel {
+    answer: String,
+}
+
+#[derive(Deserialize)]
+struct WinParameters {
+    elements: Vec<Element>,
+}
+
+#[derive(Deserialize)]
+struct Element {
+    element: GuessModel,
+}
+
+/// the post-game information returned once a guess is confirmed
+#[derive(Deserialize)]
+struct ChoiceParameters {
+    #[serde(default)]
+    times_played: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ServerListing {
+    #[serde(rename = "urlWs")]
+    url_ws: String,
+    subject_id: String,
+}
+
+/// A snapshot of everything needed to resume an akinator session later on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub uri: String,
+    pub server_url: String,
+    pub session: Option<String>,
+    pub signature: Option<String>,
+    pub frontaddr: Option<String>,
+    pub question_filter: Option<String>,
+    pub timestamp: u64,
+    pub step: usize,
+    pub progression: f32,
+    pub question: Option<String>,
+    pub theme: Theme,
+    pub language: Language,
+    pub child_mode: bool,
+    pub first_guess: Option<GuessModel>,
+    pub guesses: Vec<GuessModel>,
+    #[serde(default)]
+    pub history: Vec<HistoryEntryModel>,
+    #[serde(default)]
+    pub last_activity: Option<f64>,
+    #[serde(default)]
+    pub timed_out: bool,
+    #[serde(default)]
+    pub rejected_guesses: Vec<GuessModel>,
+    #[serde(default)]
+    pub excluded_steps: Vec<usize>,
+    #[serde(default)]
+    pub result: Option<GameResultModel>,
+    #[serde(default)]
+    pub lost: bool,
+    #[serde(default)]
+    pub question_id: Option<String>,
+    #[serde(default)]
+    pub answers: Vec<String>,
+    #[serde(default)]
+    pub session_timeout: Option<f64>,
+    #[serde(default)]
+    pub auto_renew: bool,
+}
+
+/// how long a session can go without any requests before the akinator servers are assumed to have expired it
+pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Represents a single akinator session
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub theme: Theme,
+    pub language: Language,
+    pub child_mode: bool,
+
+    pub current_question: Option<String>,
+    /// the ID of the current question on the akinator servers, if they sent one
+    pub question_id: Option<String>,
+    /// the localized labels of the answers to the current question
+    pub answers: Vec<String>,
+    pub progression: f32,
+    pub step: usize,
+    pub first_guess: Option<GuessModel>,
+    pub guesses: Vec<GuessModel>,
+    pub rejected_guesses: Vec<GuessModel>,
+    /// the steps guesses were rejected at in the current session, to exclude them again when renewing it
+    excluded_steps: Vec<usize>,
+    /// set once the guess has been confirmed with [`Game::confirm_guess`], ending the game
+    pub result: Option<GameResultModel>,
+    /// set once the akinator has run out of guesses or questions, ending the game
+    lost: bool,
+    pub history: Vec<HistoryEntryModel>,
+
+    http: Arc<HttpTransport>,
+    /// whether ``http`` belongs to this game alone, rather than being shared with other games
+    owns_http: bool,
+    transport: Arc<dyn Transport>,
+    /// the mock server ``transport`` sends the requests to, if any
+    mock_server: Option<MockServer>,
+    retry: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    guess_policy: Option<GuessPolicy>,
+    session_timeout: Duration,
+    auto_renew: bool,
+    /// the unix timestamp of the last successful request made to the session
+    last_activity: Option<f64>,
+    /// when the last successful request was made to the session, unaffected by changes to the system clock unlike ``last_activity``,
+    /// but only known for requests made since the game was created or restored
+    last_request: Option<Instant>,
+    /// whether the akinator servers have reported the session as timed out
+    timed_out: bool,
+    /// shared with the snapshots of the game, so that requests in flight are counted as soon as they complete
+    stats: Arc<Mutex<StatsModel>>,
+    closed: bool,
+    uri: String,
+    ws_url: String,
+    session: Option<String>,
+    signature: Option<String>,
+    frontaddr: Option<String>,
+    question_filter: Option<String>,
+    timestamp: u64,
+}
+
+/// strips the JSONP callback wrapping from a response body
+fn strip_callback(body: &str) -> &str {
+    match (body.find('('), body.rfind(')')) {
+        (Some(start), Some(end)) if start < end => &body[start + 1..end],
+        _ => body,
+    }
+}
+
+/// finds the substring of ``haystack`` between ``start`` and ``end``
+fn extract_between<'a>(haystack: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let from = haystack.find(start)? + start.len();
+    let to = haystack[from..].find(end)? + from;
+
+    Some(&haystack[from..to])
+}
+
+/// the name of the endpoint ``url`` points to, i.e. ``answer_api``, or ``home`` for the home page
+fn endpoint_name(url: &str) -> &str {
+    let address = url.split_once("://")
+        .map_or(url, |(_, address)| address);
+
+    match address.split_once('/') {
+        Some((_, path)) if !path.is_empty() => path.rsplit('/').next().unwrap_or(path),
+        _ => "home",
+    }
+}
+
+/// logs the outcome of a request made by the game, along with how long it took
+fn log_request<T>(endpoint: &str, url: &str, params: &[(&str, String)], latency: f64, result: &Result<T>) {
+    let step = params.iter()
+        .find(|(key, _)| *key == "step")
+        .and_then(|(_, step)| step.parse::<usize>().ok());
+
+    let at_step = step.map(|step| format!(" at step {step}")).unwrap_or_default();
+
+    match result {
+        Ok(_) => log!(
+            Level::Debug,
+            format!("{endpoint} request{at_step} completed in {:.0}ms", latency * 1000.0),
+            url = url,
+            endpoint = endpoint,
+            step = step,
+            latency = latency,
+        ),
+        Err(err) => log!(
+            Level::Warning,
+            format!("{endpoint} request{at_step} failed with {} after {:.0}ms", err.kind(), latency * 1000.0),
+            url = url,
+            endpoint = endpoint,
+            step = step,
+            latency = latency,
+            error_kind = err.kind(),
+            error = format!("{err:?}"),
+        ),
+    }
+}
+
+/// parses the parameters out of a (JSONP wrapped) response body of the akinator API
+fn parse_response<T: DeserializeOwned>(body: &str) -> Result<T> {
+    let response: Response<T> = serde_json::from_str(strip_callback(body))?;
+
+    check_completion(&response.completion)?;
+
+    response.parameters
+        .ok_or_else(|| AkiError::NoDataFound.into())
+}
+
+/// whether ``error`` shows the akinator has run out of questions to ask or guesses to make
+fn is_exhausted(error: &Error) -> bool {
+    matches!(error.inner(), Error::AkiError(AkiError::NoMoreQuestions))
+}
+
+/// the current unix timestamp in seconds
+fn unix_time() -> Result<f64> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)?
+        .as_secs_f64())
+}
+
+fn check_completion(completion: &str) -> Result<()> {
+    match completion {
+        "OK" => Ok(()),
+        "KO - SERVER DOWN" => Err(AkiError::ServersDown.into()),
+        "KO - TECHNICAL ERROR" => Err(AkiError::TechnicalError.into()),
+        "KO - TIMEOUT" => Err(AkiError::TimeoutError.into()),
+        "KO - ELEM LIST IS EMPTY" | "WARN - NO QUESTION" => Err(AkiError::NoMoreQuestions.into()),
+        _ => Err(AkiError::ConnectionError.into()),
+    }
+}
+
+impl Game {
+    pub fn new() -> Result<Self> {
+        let mut game = Self::with_shared_http(Arc::new(HttpTransport::new(HttpConfig::default())?));
+        game.owns_http = true;
+
+        Ok(game)
+    }
+
+    /// creates a game sending its requests through ``http``, an HTTP client shared with other games,
+    /// which is left open when the game is closed
+    pub fn with_shared_http(http: Arc<HttpTransport>) -> Self {
+        Self {
+            theme: Theme::Characters,
+            language: Language::English,
+            child_mode: false,
+
+            current_question: None,
+            question_id: None,
+            answers: Vec::new(),
+            progression: 0.0,
+            step: 0,
+            first_guess: None,
+            guesses: Vec::new(),
+            rejected_guesses: Vec::new(),
+            excluded_steps: Vec::new(),
+            result: None,
+            lost: false,
+            history: Vec::new(),
+
+            transport: http.clone(),
+            mock_server: None,
+            http,
+            owns_http: false,
+            retry: None,
+            rate_limiter: None,
+            guess_policy: None,
+            session_timeout: DEFAULT_SESSION_TIMEOUT,
+            auto_renew: false,
+            last_activity: None,
+            last_request: None,
+            timed_out: false,
+            stats: Arc::default(),
+            closed: false,
+            uri: String::new(),
+            ws_url: String::new(),
+            session: None,
+            signature: None,
+            frontaddr: None,
+            question_filter: None,
+            timestamp: 0,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_child_mode(mut self) -> Self {
+        self.child_mode = true;
+        self
+    }
+
+    /// sends all requests of this game through ``transport`` rather than over HTTP
+    #[must_use]
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// sends all requests of this game to ``server`` rather than to the akinator servers
+    #[must_use]
+    pub fn with_mock_server(mut self, server: MockServer) -> Self {
+        self.set_mock_server(Some(server));
+        self
+    }
+
+    /// the mock server the requests of this game are sent to, if any
+    pub const fn mock_server(&self) -> Option<&MockServer> {
+        self.mock_server.as_ref()
+    }
+
+    /// sends all requests of this game to ``server``, or back over HTTP if ``None``,
+    /// replacing any cassette being recorded or replayed
+    pub fn set_mock_server(&mut self, server: Option<MockServer>) {
+        self.transport = match &server {
+            Some(server) => server.0.clone(),
+            None => self.http.clone(),
+        };
+        self.mock_server = server;
+    }
+
+    /// the options of the HTTP client used to talk to the akinator servers
+    pub fn http_config(&self) -> Result<HttpConfig> {
+        self.http.config()
+    }
+
+    /// rebuilds the HTTP client used to talk to the akinator servers with the given options
+    pub fn set_http_config(&self, config: HttpConfig) -> Result<()> {
+        self.http.configure(config)
+    }
+
+    /// retries requests that failed with a transient error according to ``policy``
+    #[must_use]
+    pub fn with_retry(mut self, policy: Option<RetryPolicy>) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// the policy used to retry failed requests, if any
+    pub const fn retry(&self) -> Option<&RetryPolicy> {
+        self.retry.as_ref()
+    }
+
+    pub fn set_retry(&mut self, policy: Option<RetryPolicy>) {
+        self.retry = policy;
+    }
+
+    /// sends every request of this game through ``limiter``, on top of the global rate limiter (if any)
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: Option<RateLimiter>) -> Self {
+        self.rate_limiter = limiter;
+        self
+    }
+
+    /// the rate limiter of this game, if any
+    pub const fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    pub fn set_rate_limiter(&mut self, limiter: Option<RateLimiter>) {
+        self.rate_limiter = limiter;
+    }
+
+    /// lets ``policy`` decide when the akinator makes its guess, see [`Game::answer_with_policy`]
+    #[must_use]
+    pub fn with_guess_policy(mut self, policy: Option<GuessPolicy>) -> Self {
+        self.guess_policy = policy;
+        self
+    }
+
+    /// the policy deciding when the akinator makes its guess, if any
+    pub const fn guess_policy(&self) -> Option<&GuessPolicy> {
+        self.guess_policy.as_ref()
+    }
+
+    pub fn set_guess_policy(&mut self, policy: Option<GuessPolicy>) {
+        self.guess_policy = policy;
+    }
+
+    /// how long the session can go without any requests before it is assumed to have expired
+    pub const fn session_timeout(&self) -> Duration {
+        self.session_timeout
+    }
+
+    pub fn set_session_timeout(&mut self, timeout: Duration) {
+        self.session_timeout = timeout;
+    }
+
+    /// whether an expired session is renewed by replaying the answers given so far into a new one
+    pub const fn auto_renew(&self) -> bool {
+        self.auto_renew
+    }
+
+    pub fn set_auto_renew(&mut self, auto_renew: bool) {
+        self.auto_renew = auto_renew;
+    }
+
+    /// records every request made by this game (through the current transport) to a cassette file at ``path``
+    #[must_use]
+    pub fn with_recording(mut self, path: String) -> Self {
+        self.transport = Arc::new(RecordingTransport::new(self.transport, path));
+        self
+    }
+
+    /// rebuilds a game from a previously saved [`GameSnapshot`]
+    ///
+    /// only the session timeout and whether to renew automatically are restored along with the session,
+    /// the game starts out with a new HTTP client and transport, and no retry, rate limiter or guess policy,
+    /// as these are shared with other games or hold live connections; the restored game is never closed
+    pub fn from_snapshot(state: GameSnapshot) -> Result<Self> {
+        let mut game = Self::new()?
+            .with_theme(state.theme)
+            .with_language(state.language);
+
+        game.child_mode = state.child_mode;
+        game.current_question = state.question;
+        game.question_id = state.question_id;
+        game.answers = state.answers;
+        game.progression = state.progression;
+        game.step = state.step;
+        game.first_guess = state.first_guess;
+        game.guesses = state.guesses;
+        game.history = state.history;
+        game.last_activity = state.last_activity;
+        game.timed_out = state.timed_out;
+        game.rejected_guesses = state.rejected_guesses;
+        game.excluded_steps = state.excluded_steps;
+        game.result = state.result;
+        game.lost = state.lost;
+        game.auto_renew = state.auto_renew;
+
+        if let Some(timeout) = state.session_timeout {
+            game.session_timeout = timeout_from_secs(timeout)?;
+        }
+
+        game.uri = state.uri;
+        game.ws_url = state.server_url;
+        game.session = state.session;
+        game.signature = state.signature;
+        game.frontaddr = state.frontaddr;
+        game.question_filter = state.question_filter;
+        game.timestamp = state.timestamp;
+
+        Ok(game)
+    }
+
+    /// captures the current state of the game, to be restored with [`Game::from_snapshot`]
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            uri: self.uri.clone(),
+            server_url: self.ws_url.clone(),
+            session: self.session.clone(),
+            signature: self.signature.clone(),
+            frontaddr: self.frontaddr.clone(),
+            question_filter: self.question_filter.clone(),
+            timestamp: self.timestamp,
+            step: self.step,
+            progression: self.progression,
+            question: self.current_question.clone(),
+            theme: self.theme,
+            language: self.language,
+            child_mode: self.child_mode,
+            first_guess: self.first_guess.clone(),
+            guesses: self.guesses.clone(),
+            history: self.history.clone(),
+            last_activity: self.last_activity,
+            timed_out: self.timed_out,
+            rejected_guesses: self.rejected_guesses.clone(),
+            excluded_steps: self.excluded_steps.clone(),
+            result: self.result.clone(),
+            lost: self.lost,
+            question_id: self.question_id.clone(),
+            answers: self.answers.clone(),
+            session_timeout: Some(self.session_timeout.as_secs_f64()),
+            auto_renew: self.auto_renew,
+        }
+    }
+
+    /// whether the game has been closed with [`Game::close`]
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// closes the game, releasing the connections of its HTTP client unless it is shared with other games;
+    /// any further requests made by the game fail with [`Error::SessionClosed`]
+    pub fn close(&mut self) -> Result<()> {
+        self.closed = true;
+
+        if self.owns_http {
+            self.http.close()?;
+        }
+
+        Ok(())
+    }
+
+    /// remembers that a request to the session succeeded at the unix ``timestamp``
+    fn record_activity(&mut self, timestamp: f64) {
+        self.last_activity = Some(timestamp);
+        self.last_request = Some(Instant::now());
+    }
+
+    /// when the last successful request was made to the session, if it was made since the game was created or restored
+    pub const fn last_request(&self) -> Option<Instant> {
+        self.last_request
+    }
+
+    /// how many seconds it has been since the last successful request made to the session, if any
+    pub fn idle_seconds(&self) -> Result<Option<f64>> {
+        let now = unix_time()?;
+
+        Ok(self.last_activity
+            .map(|last_activity| (now - last_activity).max(0.0)))
+    }
+
+    /// whether the session has gone without any requests for longer than the session timeout,
+    /// and so has likely been expired by the akinator servers
+    pub fn is_expired(&self) -> Result<bool> {
+        Ok(self.idle_seconds()?
+            .is_some_and(|idle| idle > self.session_timeout.as_secs_f64()))
+    }
+
+    /// the statistics of the requests made over the course of the current game
+    pub fn stats(&self) -> Result<StatsModel> {
+        Ok(self.stats.lock()?.clone())
+    }
+
+    /// attaches the step and question the game is on to ``error``
+    fn in_context(&self, error: Error) -> Error {
+        error.with_context(ErrorContext {
+            step: Some(self.step),
+            question: self.current_question.clone(),
+            ..ErrorContext::default()
+        })
+    }
+
+    /// attaches the context of the game to the ``error`` a request failed with,
+    /// remembering if it shows the akinator servers have timed out the session
+    fn request_failed(&mut self, error: Error) -> Error {
+        if matches!(error.inner(), Error::AkiError(AkiError::TimeoutError)) {
+            self.timed_out = true;
+        }
+
+        self.in_context(error)
+    }
+
+    const fn ensure_open(&self) -> Result<()> {
+        if self.closed {
+            Err(Error::SessionClosed)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// the state of the game, regardless of whether its session has expired
+    const fn phase(&self) -> GameState {
+        if self.closed {
+            GameState::Closed
+        } else if self.result.is_some() {
+            GameState::Won
+        } else if self.lost {
+            GameState::Lost
+        } else if self.session.is_none() {
+            GameState::NotStarted
+        } else if self.first_guess.is_some() {
+            GameState::Guessing
+        } else {
+            GameState::InProgress
+        }
+    }
+
+    /// the state the game is in, reporting a session that timed out or went idle for longer than the session timeout as expired
+    ///
+    /// the idle time is only an estimate of when the akinator servers expire the session,
+    /// so it is never used to refuse a request, see [`Game::ensure_state`]
+    pub fn state(&self) -> Result<GameState> {
+        let phase = self.phase();
+
+        if matches!(phase, GameState::InProgress | GameState::Guessing) && (self.timed_out || self.is_expired()?) {
+            Ok(GameState::Expired)
+        } else {
+            Ok(phase)
+        }
+    }
+
+    /// fails unless the game is in one of the ``required`` states,
+    /// with [`Error::SessionClosed`] or [`Error::GameFinished`] if it has been closed or won
+    ///
+    /// a session is only considered expired once the akinator servers have timed it out,
+    /// and not even then when it is renewed automatically, as it is renewed ahead of the request
+    fn ensure_state(&self, required: &[GameState]) -> Result<()> {
+        let state = match self.phase() {
+            GameState::InProgress | GameState::Guessing if self.timed_out && !self.auto_renew => GameState::Expired,
+            phase => phase,
+        };
+
+        match state {
+            state if required.contains(&state) => Ok(()),
+            GameState::Closed => Err(Error::SessionClosed),
+            GameState::Won => Err(Error::GameFinished),
+            state => Err(Error::InvalidGameState(state, required.to_vec())),
+        }
+    }
+
+    fn callback(&self) -> String {
+        format!("jQuery331023608747682107778_{}", self.timestamp)
+    }
+
+    fn session_params(&self) -> Result<[(&'static str, String); 3]> {
+        Ok([
+            ("callback", self.callback()),
+            ("session", self.session.clone().ok_or(Error::MissingData)?),
+            ("signature", self.signature.clone().ok_or(Error::MissingData)?),
+        ])
+    }
+
+    /// runs ``operation``, retrying it according to the retry policy of the game if there is one
+    async fn retrying<T, F, Fut>(&self, idempotent: bool, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let policy = match &self.retry {
+            Some(policy) => policy,
+            None => return operation().await,
+        };
+
+        let mut attempts: usize = 0;
+        let result = policy.run(idempotent, || {
+            attempts += 1;
+            operation()
+        }).await;
+
+        self.stats.lock()?.retries += attempts.saturating_sub(1);
+        result
+    }
+
+    /// logs and records the outcome of a request to ``url`` that was sent at ``started``
+    fn finish_request<T>(&self, url: &str, params: &[(&str, String)], started: Instant, result: &Result<T>) -> Result<()> {
+        let endpoint = endpoint_name(url);
+        let latency = started.elapsed().as_secs_f64();
+
+        log_request(endpoint, url, params, latency, result);
+        self.stats.lock()?.record_request(endpoint, latency, result.is_err());
+
+        Ok(())
+    }
+
+    /// waits until the rate limiters of the game and the global one allow another request to be sent
+    async fn throttle(&self) -> Result<()> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await?;
+        }
+
+        if let Some(limiter) = RateLimiter::global()? {
+            limiter.acquire().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_text_once(&self, url: &str, params: &[(&str, String)]) -> Result<String> {
+        self.throttle().await?;
+
+        let started = Instant::now();
+        let result = self.transport.get(url, params).await;
+
+        self.finish_request(url, params, started, &result)?;
+        result
+    }
+
+    async fn get_text(&self, url: &str, params: &[(&str, String)]) -> Result<String> {
+        self.retrying(true, move || self.get_text_once(url, params))
+            .await
+    }
+
+    async fn request_once<T: DeserializeOwned>(&self, url: &str, params: &[(&str, String)]) -> Result<T> {
+        self.throttle().await?;
+
+        let started = Instant::now();
+        let result = async {
+            let body = self.transport.get(url, params).await?;
+
+            parse_response(&body)
+                .map_err(|err| err.with_context(ErrorContext::response(None, &body)))
+        }.await;
+
+        self.finish_request(url, params, started, &result)?;
+        result
+    }
+
+    /// sends a request to the akinator API and extracts its parameters
+    ///
+    /// requests that are not ``idempotent`` are only retried if the server could not have processed them
+    async fn request<T: DeserializeOwned>(&self, url: &str, params: &[(&str, String)], idempotent: bool) -> Result<T> {
+        self.retrying(idempotent, move || self.request_once(url, params))
+            .await
+    }
+
+    /// the url of the game server the current session was started on, if any
+    pub fn server(&self) -> Option<&str> {
+        Some(self.ws_url.as_str())
+            .filter(|url| !url.is_empty())
+    }
+
+    /// finds the candidate websocket API server urls for the current theme, in order of preference
+    async fn find_servers(&self) -> Result<Vec<String>> {
+        let html = self.get_text(&self.uri, &[]).await?;
+
+        let listing = extract_between(&html, "[{\"translated_theme_name\"", "}]")
+            .ok_or(AkiError::ServersDown)?;
+        let servers: Vec<ServerListing> =
+            serde_json::from_str(&format!("[{{\"translated_theme_name\"{listing}}}]"))?;
+
+        let subject_id = (self.theme as usize).to_string();
+        let mut candidates = Vec::new();
+
+        for server in servers {
+            if server.subject_id == subject_id && !candidates.contains(&server.url_ws) {
+                candidates.push(server.url_ws);
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(AkiError::NoDataFound.into());
+        }
+
+        Ok(candidates)
+    }
+
+    /// scrapes the ``uid_ext_session`` and ``frontaddr`` needed to create a new session
+    async fn session_info(&self) -> Result<(String, String)> {
+        let html = self.get_text(&format!("{}/game", self.uri), &[]).await?;
+
+        let uid = extract_between(&html, "var uid_ext_session = '", "'")
+            .ok_or(AkiError::NoDataFound)?;
+        let frontaddr = extract_between(&html, "var frontaddr = '", "'")
+            .ok_or(AkiError::NoDataFound)?;
+
+        Ok((uid.to_string(), frontaddr.to_string()))
+    }
+
+    /// the question the akinator is currently asking, if any
+    pub fn question(&self) -> Option<QuestionModel> {
+        self.current_question
+            .clone()
+            .map(|text| QuestionModel {
+                text,
+                step: self.step,
+                progression: self.progression,
+                id: self.question_id.clone(),
+                answers: self.answers.clone(),
+            })
+    }
+
+    fn update_step(&mut self, info: StepInformation) -> Result<Option<QuestionModel>> {
+        let step = info.step.parse()
+            .map_err(|_| self.in_context(Error::UpdateInfoError("step".to_string())))?;
+        let progression = info.progression.parse()
+            .map_err(|_| self.in_context(Error::UpdateInfoError("progression".to_string())))?;
+
+        self.step = step;
+        self.progression = progression;
+        self.current_question = Some(info.question);
+        self.question_id = info.question_id;
+        self.answers = info.answers
+            .into_iter()
+            .map(|label| label.answer)
+            .collect();
+
+        self.record_activity(unix_time()?);
+        self.stats.lock()?.record_step(self.step, self.progression);
+
+        Ok(self.question())
+    }
+
+    /// starts a new session and returns the first question
+    ///
+    /// if the game server for the theme is down, the session is started on the next candidate server instead,
+    /// only failing with [`AkiError::ServersDown`] once every candidate has been tried
+    pub async fn start(&mut self) -> Result<Option<QuestionModel>> {
+        self.ensure_open()?;
+
+        *self.stats.lock()? = StatsModel::default();
+        self.rejected_guesses.clear();
+        self.result = None;
+        self.lost = false;
+
+        self.start_session().await
+    }
+
+    async fn start_session(&mut self) -> Result<Option<QuestionModel>> {
+        self.uri = format!("https://{}.akinator.com", self.language.code());
+
+        let candidates = self.find_servers().await
+            .map_err(|err| self.in_context(err))?;
+        let (uid, frontaddr) = self.session_info().await
+            .map_err(|err| self.in_context(err))?;
+
+        self.timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs();
+
+        let (soft_constraint, question_filter) = if self.child_mode {
+            ("ETAT='EN'", "cat=1")
+        } else {
+            ("", "")
+        };
+
+        let mut result = Err(AkiError::ServersDown.into());
+
+        for server in candidates {
+            let params = [
+                ("callback", self.callback()),
+                ("urlApiWs", server.clone()),
+                ("partner", "1".to_string()),
+                ("childMod", self.child_mode.to_string()),
+                ("player", "website-desktop".to_string()),
+                ("uid_ext_session", uid.clone()),
+                ("frontaddr", frontaddr.clone()),
+                ("constraint", "ETAT<>'AV'".to_string()),
+                ("soft_constraint", soft_constraint.to_string()),
+                ("question_filter", question_filter.to_string()),
+            ];
+
+            result = self.request::<StartParameters>(&format!("{}/new_session", self.uri), &params, true)
+                .await
+                .map(|parameters| (server.clone(), parameters));
+
+            if !matches!(result.as_ref().map_err(Error::inner), Err(Error::AkiError(AkiError::ServersDown))) {
+                break;
+            }
+
+            log!(
+                Level::Warning,
+                format!("the game server {server} is down, trying the next candidate"),
+                server = server,
+            );
+        }
+
+        let (server, parameters) = result
+            .map_err(|err| self.in_context(err))?;
+
+        self.ws_url = server;
+        self.session = Some(parameters.identification.session);
+        self.signature = Some(parameters.identification.signature);
+        self.frontaddr = Some(frontaddr);
+        self.question_filter = Some(question_filter.to_string());
+        self.first_guess = None;
+        self.guesses.clear();
+        self.history.clear();
+        self.excluded_steps.clear();
+        self.timed_out = false;
+
+        self.update_step(parameters.step_information)
+    }
+
+    /// whether the game should renew its session and try again after failing with ``result``
+    fn should_renew<T>(&self, result: &Result<T>) -> bool {
+        self.auto_renew
+            && matches!(result.as_ref().map_err(Error::inner), Err(Error::AkiError(AkiError::TimeoutError)))
+    }
+
+    /// renews the session ahead of a request if it has timed out or has likely expired already
+    async fn renew_if_expired(&mut self) -> Result<()> {
+        if self.auto_renew && (self.timed_out || self.is_expired()?) {
+            self.renew().await?;
+        }
+
+        Ok(())
+    }
+
+    /// starts a new session and replays the answers given so far into it, resuming the game at the same step
+    ///
+    /// the guesses rejected along the way are excluded again at the steps they were rejected at,
+    /// so that the akinator does not propose them again
+    ///
+    /// fails with [`Error::SessionDiverged`] (leaving the game untouched) if the akinator asks a different question
+    /// than the one originally answered at some step, as the answers would no longer mean the same thing
+    pub async fn renew(&mut self) -> Result<()> {
+        self.ensure_state(&[GameState::InProgress, GameState::Guessing, GameState::Expired])?;
+
+        let history = self.history.clone();
+        let excluded_steps = self.excluded_steps.clone();
+        let mut renewed = self.clone();
+
+        renewed.start_session().await?;
+
+        let mut entries = history.iter();
+        let mut exclusions = excluded_steps.iter().peekable();
+
+        loop {
+            if exclusions.next_if(|step| **step == renewed.step).is_some() {
+                renewed.exclude_once().await?;
+                continue;
+            }
+
+            let entry = match entries.next() {
+                Some(entry) => entry,
+                None => break,
+            };
+
+            if renewed.step != entry.step || renewed.current_question.as_deref() != Some(entry.question.as_str()) {
+                return Err(Error::SessionDiverged(entry.step).with_context(ErrorContext {
+                    step: Some(entry.step),
+                    question: Some(entry.question.clone()),
+                    ..ErrorContext::default()
+                }));
+            }
+
+            renewed.answer_once(entry.answer).await?;
+        }
+
+        // an exclusion that could not be replayed at its step
+        if let Some(&step) = exclusions.next() {
+            return Err(Error::SessionDiverged(step).with_context(ErrorContext {
+                step: Some(step),
+                ..ErrorContext::default()
+            }));
+        }
+
+        log!(
+            Level::Info,
+            format!("renewed an expired session by replaying {} answers", history.len()),
+            step = renewed.step,
+            replayed = history.len(),
+        );
+
+        *self = renewed;
+        Ok(())
+    }
+
+    /// answers the current question and returns the next one
+    ///
+    /// the game is lost if the akinator has no questions left to ask
+    pub async fn answer(&mut self, answer: Answer) -> Result<Option<QuestionModel>> {
+        let result = self.answer_renewing(answer).await;
+
+        if let Err(err) = &result {
+            self.lost = is_exhausted(err);
+        }
+
+        result
+    }
+
+    /// answers the current question, renewing the session if it has expired, without losing the game if the akinator runs out of questions
+    async fn answer_renewing(&mut self, answer: Answer) -> Result<Option<QuestionModel>> {
+        self.ensure_state(&[GameState::InProgress])?;
+        self.renew_if_expired().await?;
+
+        match self.answer_once(answer).await {
+            result if self.should_renew(&result) => {
+                self.renew().await?;
+                self.answer_once(answer).await
+            },
+            result => result,
+        }
+    }
+
+    /// answers the current question, then lets ``policy`` decide whether the akinator should make its guess
+    ///
+    /// a guess that is not confident enough is discarded, continuing the game with the next question,
+    /// unless the policy forces a guess, or the akinator has run out of questions to ask,
+    /// in which case the game is only lost if it has no guess to make either
+    pub async fn answer_with_policy(&mut self, answer: Answer, policy: &GuessPolicy) -> Result<AnswerOutcome> {
+        let (question, exhausted) = match self.answer_renewing(answer).await {
+            Ok(question) => (question, false),
+            Err(err) if is_exhausted(&err) => (self.question(), true),
+            Err(err) => return Err(err),
+        };
+
+        let forced = exhausted || policy.forces_guess(self.step);
+
+        if !forced && !policy.triggers_guess(self.step, self.progression) {
+            return Ok(AnswerOutcome::Question(question));
+        }
+
+        let guess = self.win().await?
+            .ok_or(AkiError::NoMoreQuestions)?;
+
+        if forced || policy.accepts(&guess) {
+            log!(
+                Level::Debug,
+                format!("the guess policy proposed {} at step {}", guess.name, self.step),
+                step = self.step,
+                forced = forced,
+            );
+
+            return Ok(AnswerOutcome::Guess(guess));
+        }
+
+        // not confident enough yet, keep asking questions instead
+        self.first_guess = None;
+        self.guesses.clear();
+
+        Ok(AnswerOutcome::Question(question))
+    }
+
+    async fn answer_once(&mut self, answer: Answer) -> Result<Option<QuestionModel>> {
+        let mut params = self.session_params()?.to_vec();
+        params.extend([
+            ("urlApiWs", self.ws_url.clone()),
+            ("childMod", self.child_mode.to_string()),
+            ("step", self.step.to_string()),
+            ("answer", (answer as u8).to_string()),
+            ("frontaddr", self.frontaddr.clone().ok_or(Error::MissingData)?),
+            ("question_filter", self.question_filter.clone().unwrap_or_default()),
+        ]);
+
+        let info: StepInformation = self.request(&format!("{}/answer_api", self.uri), &params, false).await
+            .map_err(|err| self.request_failed(err))?;
+
+        let step = self.step;
+        let question = self.current_question.clone().unwrap_or_default();
+        let progression_before = self.progression;
+
+        let next = self.update_step(info)?;
+
+        self.history.push(HistoryEntryModel {
+            step,
+            question,
+            answer,
+            progression_before,
+            progression_after: self.progression,
+            timestamp: unix_time()?,
+        });
+
+        Ok(next)
+    }
+
+    /// ends the game, fetching the akinator's guesses and returning the best one
+    ///
+    /// the game is lost if the akinator has no guesses to make
+    pub async fn win(&mut self) -> Result<Option<GuessModel>> {
+        self.ensure_state(&[GameState::InProgress, GameState::Guessing])?;
+        self.renew_if_expired().await?;
+
+        let result = match self.win_once().await {
+            result if self.should_renew(&result) => {
+                self.renew().await?;
+                self.win_once().await
+            },
+            result => result,
+        };
+
+        self.lose_on_exhaustion(&result);
+        result
+    }
+
+    /// marks the game as lost if ``result`` shows the akinator has run out of guesses or questions
+    fn lose_on_exhaustion<T>(&mut self, result: &Result<Option<T>>) {
+        self.lost = match result {
+            Ok(next) => next.is_none(),
+            Err(err) => is_exhausted(err),
+        };
+    }
+
+    async fn win_once(&mut self) -> Result<Option<GuessModel>> {
+        let mut params = self.session_params()?.to_vec();
+        params.extend([
+            ("step", self.step.to_string()),
+            ("size", "2".to_string()),
+            ("max_pic_width", "246".to_string()),
+            ("max_pic_height", "294".to_string()),
+            ("pref_photos", "VO-OK".to_string()),
+            ("duel_allowed", "1".to_string()),
+            ("mode_question", "0".to_string()),
+        ]);
+
+        let parameters: WinParameters = self.request(&format!("{}/list", self.ws_url), &params, true).await
+            .map_err(|err| self.request_failed(err))?;
+
+        self.guesses = parameters.elements
+            .into_iter()
+            .map(|element| element.element)
+            .collect();
+        self.first_guess = self.guesses.first().cloned();
+        self.record_activity(unix_time()?);
+
+        Ok(self.first_guess.clone())
+    }
+
+    /// goes back a question and returns the previous question
+    ///
+    /// going back from a guess discards it, continuing the game from the previous question
+    pub async fn back(&mut self) -> Result<Option<QuestionModel>> {
+        self.ensure_state(&[GameState::InProgress, GameState::Guessing])?;
+
+        if self.step == 0 {
+            return Err(AkiError::CantGoBackAnyFurther.into());
+        }
+
+        self.renew_if_expired().await?;
+
+        match self.back_once().await {
+            result if self.should_renew(&result) => {
+                self.renew().await?;
+                self.back_once().await
+            },
+            result => result,
+        }
+    }
+
+    async fn back_once(&mut self) -> Result<Option<QuestionModel>> {
+        let mut params = self.session_params()?.to_vec();
+        params.extend([
+            ("step", self.step.to_string()),
+            ("answer", "-1".to_string()),
+            ("childMod", self.child_mode.to_string()),
+            ("question_filter", self.question_filter.clone().unwrap_or_default()),
+        ]);
+
+        let info: StepInformation = self.request(&format!("{}/cancel_answer", self.ws_url), &params, false).await
+            .map_err(|err| self.request_failed(err))?;
+        let previous = self.update_step(info)?;
+
+        // drop the answers given at or after the step we went back to
+        let step = self.step;
+        self.history.retain(|entry| entry.step < step);
+        self.excluded_steps.retain(|excluded| *excluded < step);
+        self.first_guess = None;
+        self.guesses.clear();
+
+        self.stats.lock()?.backs += 1;
+
+        Ok(previous)
+    }
+
+    /// rejects the guess proposed by [`Game::win`], excluding it from the akinator's next proposals,
+    /// and continues asking questions from the current step, returning the next question
+    ///
+    /// the game is lost if the akinator has no questions left to ask
+    pub async fn reject_guess(&mut self) -> Result<Option<QuestionModel>> {
+        self.ensure_state(&[GameState::Guessing])?;
+
+        let guess = self.first_guess
+            .clone()
+            .ok_or(Error::MissingData)?;
+
+        let next = self.exclude_once().await;
+
+        if let Err(err) = &next {
+            self.lost = is_exhausted(err);
+        }
+
+        let next = next?;
+
+        self.rejected_guesses.push(guess);
+        self.first_guess = None;
+        self.guesses.clear();
+
+        Ok(next)
+    }
+
+    /// excludes the guesses proposed at the current step from the akinator's next proposals, moving on to the next question
+    async fn exclude_once(&mut self) -> Result<Option<QuestionModel>> {
+        let mut params = self.session_params()?.to_vec();
+        params.extend([
+            ("step", self.step.to_string()),
+            ("forward_answer", "1".to_string()),
+            ("question_filter", self.question_filter.clone().unwrap_or_default()),
+        ]);
+
+        let info: StepInformation = self.request(&format!("{}/exclusion", self.ws_url), &params, false).await
+            .map_err(|err| self.request_failed(err))?;
+
+        let step = self.step;
+        let next = self.update_step(info)?;
+        self.excluded_steps.push(step);
+
+        Ok(next)
+    }
+
+    /// confirms that ``guess`` (or the guess proposed by [`Game::win`] if ``None``) is correct,
+    /// acknowledging the win to the akinator servers and ending the game
+    pub async fn confirm_guess(&mut self, guess: Option<GuessModel>) -> Result<GameResultModel> {
+        self.ensure_state(&[GameState::Guessing])?;
+
+        let guess = guess
+            .or_else(|| self.first_guess.clone())
+            .ok_or(Error::MissingData)?;
+
+        let mut params = self.session_params()?.to_vec();
+        params.extend([
+            ("step", self.step.to_string()),
+            ("element", guess.id.clone()),
+            ("duel_allowed", "1".to_string()),
+        ]);
+
+        let parameters: ChoiceParameters = self.request(&format!("{}/choice", self.ws_url), &params, false).await
+            .map_err(|err| self.request_failed(err))?;
+
+        let timestamp = unix_time()?;
+        self.record_activity(timestamp);
+
+        let result = GameResultModel {
+            guess,
+            step: self.step,
+            times_played: parameters.times_played.and_then(|times| times.parse().ok()),
+            timestamp,
+        };
+        self.result = Some(result.clone());
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,139 @@
+use crate::models::GuessModel;
+
+use pyo3::{
+    prelude::*,
+    exceptions::PyValueError,
+};
+
+/// A policy deciding when the akinator should make its guess, evaluated by ``answer`` after each question
+///
+/// Once the ``progression`` reaches the threshold (or the game reaches the ``fallback_step``),
+/// the akinator's best guess is fetched and proposed if it is at least as confident as ``confidence``,
+/// otherwise it is discarded and the game goes on with the next question.
+/// Once the game reaches ``max_steps`` (or the akinator runs out of questions), the best guess is proposed regardless of its confidence
+///
+/// Parameters
+/// ----------
+/// progression : Optional[:class:`float`]
+///     the progression (between ``0.0`` and ``100.0``) at which the akinator should try to guess,
+///     defaults to ``80.0``
+/// confidence : Optional[:class:`float`]
+///     the minimum confidence (between ``0.0`` and ``1.0``) of a guess for it to be proposed,
+///     defaults to ``0.0``
+/// max_steps : Optional[:class:`int`]
+///     the step at which a guess is proposed whatever its confidence,
+///     defaults to ``None`` (no limit)
+/// fallback_step : Optional[:class:`int`]
+///     the step from which the akinator tries to guess after every question, even if the progression is below the threshold,
+///     defaults to ``None`` (only the progression is considered)
+///
+/// Raises
+/// ------
+/// :class:`ValueError`
+///     ``progression`` or ``confidence`` is out of range
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+#[pyo3(text_signature = "(*, progression = None, confidence = None, max_steps = None, fallback_step = None)")]
+pub struct GuessPolicy {
+    progression: f32,
+    confidence: f32,
+    max_steps: Option<usize>,
+    fallback_step: Option<usize>,
+}
+
+impl Default for GuessPolicy {
+    fn default() -> Self {
+        Self {
+            progression: 80.0,
+            confidence: 0.0,
+            max_steps: None,
+            fallback_step: None,
+        }
+    }
+}
+
+impl GuessPolicy {
+    /// whether the akinator should try to guess at ``step`` with the given ``progression``
+    pub fn triggers_guess(&self, step: usize, progression: f32) -> bool {
+        progression >= self.progression
+            || self.fallback_step.is_some_and(|fallback| step >= fallback)
+    }
+
+    /// whether a guess has to be proposed at ``step`` regardless of its confidence
+    pub fn forces_guess(&self, step: usize) -> bool {
+        self.max_steps
+            .is_some_and(|max_steps| step >= max_steps)
+    }
+
+    /// whether ``guess`` is confident enough to be proposed
+    pub fn accepts(&self, guess: &GuessModel) -> bool {
+        guess.confidence
+            .parse::<f32>()
+            .is_ok_and(|confidence| confidence >= self.confidence)
+    }
+}
+
+#[pymethods]
+impl GuessPolicy {
+    #[new]
+    #[args("*", progression, confidence, max_steps, fallback_step)]
+    fn constructor(
+        progression: Option<f32>,
+        confidence: Option<f32>,
+        max_steps: Option<usize>,
+        fallback_step: Option<usize>,
+    ) -> PyResult<Self> {
+        let defaults = Self::default();
+        let progression = progression.unwrap_or(defaults.progression);
+        let confidence = confidence.unwrap_or(defaults.confidence);
+
+        if !(0.0..=100.0).contains(&progression) {
+            return Err(PyValueError::new_err("`progression` must be between 0.0 and 100.0"));
+        }
+
+        if !(0.0..=1.0).contains(&confidence) {
+            return Err(PyValueError::new_err("`confidence` must be between 0.0 and 1.0"));
+        }
+
+        Ok(Self {
+            progression,
+            confidence,
+            max_steps,
+            fallback_step,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<GuessPolicy progression={} confidence={} max_steps={:?} fallback_step={:?}>",
+            self.progression,
+            self.confidence,
+            self.max_steps,
+            self.fallback_step,
+        )
+    }
+
+    /// :class:`float`: the progression at which the akinator tries to guess
+    #[getter]
+    const fn progression(&self) -> f32 {
+        self.progression
+    }
+
+    /// :class:`float`: the minimum confidence of a guess for it to be proposed
+    #[getter]
+    const fn confidence(&self) -> f32 {
+        self.confidence
+    }
+
+    /// Optional[:class:`int`]: the step at which a guess is proposed whatever its confidence
+    #[getter]
+    const fn max_steps(&self) -> Option<usize> {
+        self.max_steps
+    }
+
+    /// Optional[:class:`int`]: the step from which the akinator tries to guess after every question
+    #[getter]
+    const fn fallback_step(&self) -> Option<usize> {
+        self.fallback_step
+    }
+}
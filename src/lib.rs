@@ -1,16 +1,32 @@
-//! Python bindings for ``akinator-rs``, a wrapper around the undocumented akinator API
+//! Python bindings for the undocumented akinator API, built on top of ``akinator-rs``
 //!
 //! designed for easy implementation of an akinator game in code, providing a simple and easy to use API.
+//!
+//! the game client itself talks to the API directly (see [`game`]), as the session state has to be saved, restored and renewed,
+//! while the enums and errors of ``akinator-rs`` are still shared with it
 
 use crate::{
     enums::{
         Theme,
         Answer,
         Language,
+        GameState,
     },
     async_akinator::AsyncAkinator,
     blocking_akinator::Akinator,
-    models::Guess,
+    mock::MockServer,
+    pool::AkinatorPool,
+    guess_policy::GuessPolicy,
+    models::{
+        Guess,
+        AnswerResult,
+        GameResult,
+        HistoryEntry,
+        Question,
+    },
+    rate_limit::RateLimiter,
+    retry::RetryPolicy,
+    stats::GameStats,
 };
 
 use pyo3::prelude::*;
@@ -19,7 +35,20 @@ pub mod blocking_akinator;
 pub mod async_akinator;
 pub mod enums;
 pub mod error;
+pub mod game;
+pub mod guess_policy;
+pub mod mock;
 pub mod models;
+pub mod pool;
+pub mod question;
+pub mod rate_limit;
+pub mod retry;
+pub mod runtime;
+pub mod stats;
+mod logging;
+mod shared;
+mod state;
+pub mod transport;
 
 
 /// Python bindings for ``akinator-rs``, a wrapper around the undocumented akinator API
@@ -29,13 +58,29 @@ pub mod models;
 fn akinator(py: Python<'_>, module: &PyModule) -> PyResult<()> {
     module.add_class::<AsyncAkinator>()?;
     module.add_class::<Akinator>()?;
+    module.add_class::<AkinatorPool>()?;
     module.add_class::<Guess>()?;
+    module.add_class::<HistoryEntry>()?;
+    module.add_class::<GameResult>()?;
+    module.add_class::<AnswerResult>()?;
+    module.add_class::<Question>()?;
+    module.add_class::<MockServer>()?;
+    module.add_class::<RetryPolicy>()?;
+    module.add_class::<GuessPolicy>()?;
+    module.add_class::<RateLimiter>()?;
+    module.add_class::<GameStats>()?;
 
     module.add_class::<Theme>()?;
     module.add_class::<Answer>()?;
     module.add_class::<Language>()?;
+    module.add_class::<GameState>()?;
+
+    module.add_function(wrap_pyfunction!(runtime::configure_runtime, module)?)?;
+    module.add_function(wrap_pyfunction!(rate_limit::set_rate_limiter, module)?)?;
+    module.add_function(wrap_pyfunction!(rate_limit::get_rate_limiter, module)?)?;
 
     error::add_exceptions(py, module)?;
+    logging::init(py)?;
 
     Ok(())
 }
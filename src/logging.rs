@@ -0,0 +1,100 @@
+//! Forwards records about the requests made by games to python's ``logging.getLogger("akinator")``
+//!
+//! the fields of each record (such as the request url, step or latency) are passed in the ``extra`` mapping,
+//! so they are available as attributes on the python ``LogRecord``, i.e. for structured log handlers
+//!
+//! the logger is asked whether it is enabled for the level of each record, so a level change applies to the very next record
+
+use pyo3::{
+    prelude::*,
+    once_cell::GILOnceCell,
+    types::{
+        PyDict,
+        IntoPyDict,
+    },
+};
+
+/// the name of the python logger records are sent to
+const LOGGER_NAME: &str = "akinator";
+
+/// the ``akinator`` python logger, looked up on first use
+static LOGGER: GILOnceCell<PyObject> = GILOnceCell::new();
+
+/// the python logging levels records can be emitted at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Debug = 10,
+    Info = 20,
+    Warning = 30,
+}
+
+/// attaches a ``NullHandler`` to the ``akinator`` logger, as is recommended for libraries,
+/// so that its records are not printed to stderr unless logging has been configured
+pub(crate) fn init(py: Python<'_>) -> PyResult<()> {
+    let handler = py.import("logging")?
+        .call_method0("NullHandler")?;
+
+    logger(py)?
+        .call_method1("addHandler", (handler,))?;
+
+    Ok(())
+}
+
+/// emits a record to the ``akinator`` python logger, with the given ``key = value`` fields as extra attributes
+macro_rules! log {
+    ( $level:expr, $message:expr $(, $key:ident = $value:expr )* $(,)? ) => {
+        $crate::logging::emit_record(
+            $level,
+            &$message,
+            &[ $( (stringify!($key), &$value as &dyn ::pyo3::ToPyObject) ),* ],
+        )
+    };
+}
+
+pub(crate) use log;
+
+/// emits a record to the ``akinator`` python logger, if it is enabled for ``level``
+///
+/// errors raised by python while logging are printed rather than raised, as logging should never break a game
+pub(crate) fn emit_record(level: Level, message: &str, fields: &[(&str, &dyn ToPyObject)]) {
+    Python::with_gil(|py| {
+        if let Err(err) = emit(py, level, message, fields) {
+            err.print(py);
+        }
+    });
+}
+
+/// the ``akinator`` python logger, cached in [`LOGGER`]
+fn logger(py: Python<'_>) -> PyResult<&PyAny> {
+    if let Some(logger) = LOGGER.get(py) {
+        return Ok(logger.as_ref(py));
+    }
+
+    let logger: PyObject = py.import("logging")?
+        .call_method1("getLogger", (LOGGER_NAME,))?
+        .into();
+
+    Ok(LOGGER.get_or_init(py, || logger).as_ref(py))
+}
+
+fn emit(py: Python<'_>, level: Level, message: &str, fields: &[(&str, &dyn ToPyObject)]) -> PyResult<()> {
+    let logger = logger(py)?;
+
+    if !logger.call_method1("isEnabledFor", (level as u8,))?.is_true()? {
+        return Ok(());
+    }
+
+    let extra = PyDict::new(py);
+
+    for (key, value) in fields {
+        extra.set_item(key, value.to_object(py))?;
+    }
+
+    logger.call_method(
+        "log",
+        (level as u8, message),
+        Some([("extra", extra)].into_py_dict(py)),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,586 @@
+//! A scripted stand-in for the akinator servers,
+//! so that games can be played (and tested) without any network access
+
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    state::PyState,
+    transport::{
+        Transport,
+        ResponseFuture,
+        timeout_from_secs,
+    },
+};
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+use serde_json::{
+    json,
+    Value,
+};
+use pyo3::{
+    prelude::*,
+    exceptions::PyValueError,
+};
+
+const SERVER_URL: &str = "https://mock.akinator.local/ws";
+
+const DEFAULT_QUESTIONS: [&str; 5] = [
+    "Is your character real?",
+    "Is your character a man?",
+    "Is your character from a video game?",
+    "Does your character wear a hat?",
+    "Is your character a plumber?",
+];
+
+const ANSWER_NAMES: [&str; 5] = [
+    "Yes",
+    "No",
+    "Idk",
+    "Probably",
+    "ProbablyNot",
+];
+
+/// the answer labels sent along with every question, as the english akinator servers do
+const ANSWER_LABELS: [&str; 5] = [
+    "Yes",
+    "No",
+    "Don't know",
+    "Probably",
+    "Probably not",
+];
+
+/// the completion string the mock responds with for each injectable error kind
+fn completion_for(error: &str) -> Option<&'static str> {
+    match error {
+        "ServersDown" => Some("KO - SERVER DOWN"),
+        "TechnicalError" => Some("KO - TECHNICAL ERROR"),
+        "TimeoutError" => Some("KO - TIMEOUT"),
+        "NoMoreQuestions" => Some("WARN - NO QUESTION"),
+        "ConnectionError" => Some("KO - CONNECTION ERROR"),
+        _ => None,
+    }
+}
+
+fn parse_error(error: &str) -> PyResult<&'static str> {
+    completion_for(error)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown error kind to inject: {error:?}")))
+}
+
+fn param<'a>(params: &'a [(&str, String)], key: &str) -> Option<&'a str> {
+    params.iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// fills in the fields missing from a user provided guess
+fn guess_data(index: usize, guess: &Value) -> Value {
+    let mut data = json!({
+        "id": (index + 1).to_string(),
+        "name": format!("Mock guess #{}", index + 1),
+        "award_id": "-1",
+        "flag_photo": 0,
+        "confidence": "0.95",
+        "description": "",
+        "ranking": (index + 1).to_string(),
+        "picture_path": "",
+        "absolute_picture_path": "",
+    });
+
+    if let (Some(data), Some(overrides)) = (data.as_object_mut(), guess.as_object()) {
+        for (key, value) in overrides {
+            let key = if key == "proba" { "confidence" } else { key };
+            let value = match value {
+                Value::Number(number) if key != "flag_photo" => Value::String(number.to_string()),
+                _ => value.clone(),
+            };
+
+            data.insert(key.to_string(), value);
+        }
+    }
+
+    data
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    /// the questions asked so far in each session, indexed by step
+    asked: HashMap<String, Vec<String>>,
+    /// the number of guesses rejected so far in each session, which are left out of its next proposals
+    excluded: HashMap<String, usize>,
+    /// how many times each guess was confirmed, across sessions
+    played: HashMap<String, usize>,
+    /// completions to respond with for the next requests, regardless of step
+    pending_errors: VecDeque<&'static str>,
+    /// completions to respond with (once) when a request is made at a specific step
+    step_errors: HashMap<usize, &'static str>,
+    requests: Vec<String>,
+    sessions: usize,
+    /// the number of requests currently waiting on the latency of the server
+    in_flight: usize,
+    /// the most requests that were ever in flight at once
+    max_in_flight: usize,
+}
+
+/// the shared backend of a [`MockServer`], acting as the game's [`Transport`]
+#[derive(Debug)]
+pub struct MockBackend {
+    questions: Vec<String>,
+    servers: Vec<String>,
+    down_servers: Vec<String>,
+    latency: Option<Duration>,
+    tree: HashMap<String, HashMap<String, String>>,
+    progression: Vec<f32>,
+    guesses: Vec<Value>,
+    state: Mutex<MockState>,
+}
+
+impl MockBackend {
+    fn progression_at(&self, step: usize) -> f32 {
+        if self.progression.is_empty() {
+            #[allow(clippy::cast_precision_loss)]
+            let linear = step as f32 / self.questions.len().max(1) as f32 * 100.0;
+
+            linear.min(100.0)
+        } else {
+            self.progression[step.min(self.progression.len() - 1)]
+        }
+    }
+
+    /// an ID for ``question`` that stays the same for the lifetime of the server, its position among the questions (or the branches of the tree)
+    fn question_id(&self, question: &str) -> usize {
+        self.questions
+            .iter()
+            .chain(self.tree.values().flat_map(HashMap::values))
+            .position(|asked| asked == question)
+            .map_or(0, |index| index + 1)
+    }
+
+    fn step_information(&self, question: &str, step: usize) -> Value {
+        json!({
+            "question": question,
+            "step": step.to_string(),
+            "progression": format!("{:.5}", self.progression_at(step)),
+            "questionid": self.question_id(question).to_string(),
+            "answers": ANSWER_LABELS
+                .iter()
+                .map(|label| json!({ "answer": label }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// the question to ask after ``question`` (asked at ``step``) was answered with ``answer``
+    fn next_question(&self, question: &str, step: usize, answer: &str) -> Option<String> {
+        self.tree.get(question)
+            .and_then(|branches| branches.get(answer))
+            .or_else(|| self.questions.get(step + 1))
+            .cloned()
+    }
+
+    /// whether a request to ``url`` (or to create a session on ``url``) should be answered as if the server was down
+    fn is_down(&self, url: &str) -> bool {
+        self.down_servers
+            .iter()
+            .any(|server| url.starts_with(server.as_str()))
+    }
+
+    fn respond(&self, url: &str, params: &[(&str, String)]) -> Result<String> {
+        let path = url.split_once("://")
+            .map_or(url, |(_, rest)| rest);
+        let endpoint = path.split_once('/')
+            .map_or("", |(_, path)| path)
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+
+        match endpoint {
+            "" => {
+                // formatted by hand, as the server listing is scraped with its keys in this exact order
+                let servers = [("Characters", 1), ("Animals", 14), ("Objects", 2)]
+                    .iter()
+                    .flat_map(|(name, id)| self.servers.iter().map(move |server| format!(
+                        "{{\"translated_theme_name\":\"{name}\",\"urlWs\":\"{}\",\"subject_id\":\"{id}\"}}",
+                        server.replace('/', "\\/"),
+                    )))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                return Ok(format!("<script>var themes = [{servers}];</script>"));
+            },
+            "game" => return Ok(
+                "<script>\n    var uid_ext_session = 'mock-uid';\n    var frontaddr = 'mock-frontaddr';\n</script>".to_string()
+            ),
+            _ => (),
+        }
+
+        let mut state = self.state.lock()?;
+        state.requests.push(endpoint.to_string());
+
+        let step = param(params, "step")
+            .and_then(|step| step.parse::<usize>().ok());
+
+        // only requests made at a step can fail with the errors of a step, unlike starting a session
+        let injected = state.pending_errors
+            .pop_front()
+            .or_else(|| step.and_then(|step| state.step_errors.remove(&step)));
+
+        let step = step.unwrap_or_default();
+
+        let callback = param(params, "callback")
+            .unwrap_or("callback");
+        let session = param(params, "session")
+            .unwrap_or_default();
+
+        let server = if endpoint == "new_session" {
+            param(params, "urlApiWs").unwrap_or_default()
+        } else {
+            url
+        };
+
+        let response = if let Some(completion) = injected {
+            json!({ "completion": completion })
+        } else if self.is_down(server) {
+            json!({ "completion": "KO - SERVER DOWN" })
+        } else if endpoint == "new_session" {
+            if let Some(first) = self.questions.first() {
+                state.sessions += 1;
+
+                let session = state.sessions.to_string();
+                state.asked.insert(session.clone(), vec![first.clone()]);
+
+                json!({
+                    "completion": "OK",
+                    "parameters": {
+                        "identification": {
+                            "session": session,
+                            "signature": "424242",
+                        },
+                        "step_information": self.step_information(first, 0),
+                    },
+                })
+            } else {
+                json!({ "completion": "WARN - NO QUESTION" })
+            }
+        } else if let Some(asked) = state.asked.get_mut(session) {
+            match endpoint {
+                "answer_api" => {
+                    let answer = param(params, "answer")
+                        .and_then(|answer| answer.parse::<usize>().ok())
+                        .and_then(|answer| ANSWER_NAMES.get(answer));
+
+                    match (asked.get(step).cloned(), answer) {
+                        (Some(question), Some(answer)) => {
+                            asked.truncate(step + 1);
+
+                            if let Some(next) = self.next_question(&question, step, answer) {
+                                let info = self.step_information(&next, step + 1);
+                                asked.push(next);
+
+                                json!({ "completion": "OK", "parameters": info })
+                            } else {
+                                json!({ "completion": "WARN - NO QUESTION" })
+                            }
+                        },
+                        _ => json!({ "completion": "KO - TIMEOUT" }),
+                    }
+                },
+                "cancel_answer" => if step == 0 || step > asked.len() {
+                    json!({ "completion": "KO - TIMEOUT" })
+                } else {
+                    asked.truncate(step);
+                    let info = self.step_information(&asked[step - 1], step - 1);
+
+                    json!({ "completion": "OK", "parameters": info })
+                },
+                "exclusion" => match asked.get(step) {
+                    Some(_) => {
+                        asked.truncate(step + 1);
+
+                        if let Some(next) = self.questions.get(step + 1).cloned() {
+                            let info = self.step_information(&next, step + 1);
+                            asked.push(next);
+                            *state.excluded.entry(session.to_string()).or_default() += 1;
+
+                            json!({ "completion": "OK", "parameters": info })
+                        } else {
+                            json!({ "completion": "WARN - NO QUESTION" })
+                        }
+                    },
+                    None => json!({ "completion": "KO - TIMEOUT" }),
+                },
+                "choice" => match param(params, "element") {
+                    Some(element) => {
+                        let played = state.played
+                            .entry(element.to_string())
+                            .or_default();
+                        *played += 1;
+
+                        json!({
+                            "completion": "OK",
+                            "parameters": { "times_played": played.to_string() },
+                        })
+                    },
+                    None => json!({ "completion": "KO - TECHNICAL ERROR" }),
+                },
+                "list" => {
+                    let excluded = state.excluded
+                        .get(session)
+                        .copied()
+                        .unwrap_or_default();
+
+                    let elements = self.guesses
+                        .iter()
+                        .enumerate()
+                        .skip(excluded)
+                        .map(|(index, guess)| json!({ "element": guess_data(index, guess) }))
+                        .collect::<Vec<_>>();
+
+                    if elements.is_empty() {
+                        json!({ "completion": "KO - ELEM LIST IS EMPTY" })
+                    } else {
+                        json!({
+                            "completion": "OK",
+                            "parameters": {
+                                "elements": elements,
+                                "NbObjetsPertinents": elements.len().to_string(),
+                            },
+                        })
+                    }
+                },
+                _ => json!({ "completion": "KO - UNKNOWN ENDPOINT" }),
+            }
+        } else {
+            // sessions that were never started (or have been expired) time out, as they do on the akinator servers
+            json!({ "completion": "KO - TIMEOUT" })
+        };
+
+        Ok(format!("{callback}({response})"))
+    }
+}
+
+/// counts a request to the mock server as in flight for as long as it is alive,
+/// including when it is dropped midway, such as when its task is cancelled
+struct InFlight<'a>(&'a Mutex<MockState>);
+
+impl<'a> InFlight<'a> {
+    fn start(state: &'a Mutex<MockState>) -> Result<Self> {
+        let mut locked = state.lock()?;
+        locked.in_flight += 1;
+        locked.max_in_flight = locked.max_in_flight.max(locked.in_flight);
+
+        Ok(Self(state))
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.0.lock() {
+            state.in_flight -= 1;
+        }
+    }
+}
+
+impl Transport for MockBackend {
+    fn get<'a>(&'a self, url: &'a str, params: &'a [(&'a str, String)]) -> ResponseFuture<'a> {
+        Box::pin(async move {
+            let _in_flight = InFlight::start(&self.state)?;
+
+            if let Some(latency) = self.latency {
+                tokio::time::sleep(latency).await;
+            }
+
+            self.respond(url, params)
+        })
+    }
+}
+
+/// A scripted stand-in for the akinator servers, to be passed as the ``transport``
+/// of an :class:`Akinator` or :class:`AsyncAkinator` to play games without any network access,
+/// i.e. for testing
+///
+/// Parameters
+/// ----------
+/// questions : Optional[List[:class:`str`]]
+///     the questions to ask, in order, defaults to a small set of generic questions
+/// tree : Optional[Dict[:class:`str`, Dict[:class:`str`, :class:`str`]]]
+///     maps a question to the next question to ask for each answer given to it,
+///     keyed by the answer's name (``"Yes"``, ``"No"``, ``"Idk"``, ``"Probably"`` or ``"ProbablyNot"``),
+///     falling back to the order of ``questions`` when there is no matching branch
+/// progression : Optional[List[:class:`float`]]
+///     the progression to report at each step, the last value is repeated past the end of the list,
+///     defaults to a linear curve reaching ``100.0`` at the last question
+/// guesses : Optional[List[Dict[:class:`str`, Any]]]
+///     the guesses returned by ``win``, keyed by the same fields as :class:`Guess`,
+///     any missing fields are filled in, defaults to a single placeholder guess,
+///     each guess rejected with ``reject_guess`` is left out of the guesses returned afterwards,
+///     and confirming a guess with ``confirm_guess`` reports how many times it was confirmed on this server so far
+/// errors : Optional[Dict[:class:`int`, :class:`str`]]
+///     errors to respond with once a request is made at the given step, such as answering the question asked at it,
+///     which starting a session never fails with,
+///     one of ``"ServersDown"``, ``"TechnicalError"``, ``"TimeoutError"``, ``"NoMoreQuestions"`` or ``"ConnectionError"``
+/// servers : Optional[List[:class:`str`]]
+///     the urls of the game servers listed for every theme, in order,
+///     defaults to a single placeholder server
+/// down_servers : Optional[List[:class:`str`]]
+///     the urls of the listed game servers that respond as if they were down,
+///     defaults to ``None``
+/// latency : Optional[:class:`float`]
+///     a delay in seconds to wait for before responding to each request,
+///     defaults to ``None`` (responding immediately)
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+#[pyo3(text_signature = "(*, questions = None, tree = None, progression = None, guesses = None, errors = None, servers = None, down_servers = None, latency = None)")]
+pub struct MockServer(
+    pub Arc<MockBackend>,
+);
+
+#[pymethods]
+impl MockServer {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[args("*", questions, tree, progression, guesses, errors, servers, down_servers, latency)]
+    fn constructor(
+        questions: Option<Vec<String>>,
+        tree: Option<HashMap<String, HashMap<String, String>>>,
+        progression: Option<Vec<f32>>,
+        guesses: Option<Vec<&PyAny>>,
+        errors: Option<HashMap<usize, String>>,
+        servers: Option<Vec<String>>,
+        down_servers: Option<Vec<String>>,
+        latency: Option<f64>,
+    ) -> PyResult<Self> {
+        let questions = questions.unwrap_or_else(||
+            DEFAULT_QUESTIONS
+                .map(String::from)
+                .to_vec()
+        );
+
+        let guesses = match guesses {
+            Some(guesses) => guesses
+                .into_iter()
+                .map(Value::from_py)
+                .collect::<PyResult<Vec<_>>>()?,
+            None => vec![json!({ "name": "Mock guess" })],
+        };
+
+        let step_errors = errors
+            .unwrap_or_default()
+            .iter()
+            .map(|(step, error)| Ok((*step, parse_error(error)?)))
+            .collect::<PyResult<HashMap<_, _>>>()?;
+
+        Ok(Self(Arc::new(MockBackend {
+            questions,
+            servers: servers.unwrap_or_else(|| vec![SERVER_URL.to_string()]),
+            down_servers: down_servers.unwrap_or_default(),
+            latency: latency.map(timeout_from_secs).transpose()?,
+            tree: tree.unwrap_or_default(),
+            progression: progression.unwrap_or_default(),
+            guesses,
+            state: Mutex::new(MockState {
+                step_errors,
+                ..MockState::default()
+            }),
+        })))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<MockServer questions={} guesses={}>",
+            self.0.questions.len(),
+            self.0.guesses.len(),
+        )
+    }
+
+    /// Makes the next ``times`` requests to the mock server respond with ``error``
+    ///
+    /// Parameters
+    /// ----------
+    /// error : :class:`str`
+    ///     the kind of error to respond with,
+    ///     one of ``"ServersDown"``, ``"TechnicalError"``, ``"TimeoutError"``, ``"NoMoreQuestions"`` or ``"ConnectionError"``
+    /// times : :class:`int`
+    ///     the number of requests to respond to with the error, defaults to ``1``
+    ///
+    /// Raises
+    /// ------
+    /// :class:`ValueError`
+    ///     the given error kind is unknown
+    #[args(times = "1")]
+    #[pyo3(text_signature = "(self, error, times = 1)")]
+    fn inject_error(&self, error: &str, times: usize) -> PyResult<()> {
+        let completion = parse_error(error)?;
+
+        self.0.state
+            .lock()
+            .map_err(Error::from)?
+            .pending_errors
+            .extend(std::iter::repeat(completion).take(times));
+
+        Ok(())
+    }
+
+    /// Expires every session started so far, as the akinator servers do after a while,
+    /// so that any further requests made to them fail with :class:`TimeoutError`
+    fn expire_sessions(&self) -> PyResult<()> {
+        self.0.state
+            .lock()
+            .map_err(Error::from)?
+            .asked
+            .clear();
+
+        Ok(())
+    }
+
+    /// List[:class:`str`]: the API endpoints requested so far, in order
+    #[getter]
+    fn requests(&self) -> PyResult<Vec<String>> {
+        let state = self.0.state
+            .lock()
+            .map_err(Error::from)?;
+
+        Ok(state.requests.clone())
+    }
+
+    /// :class:`int`: the number of game sessions started on this mock server
+    #[getter]
+    fn sessions(&self) -> PyResult<usize> {
+        let state = self.0.state
+            .lock()
+            .map_err(Error::from)?;
+
+        Ok(state.sessions)
+    }
+
+    /// :class:`int`: the number of requests the server is currently responding to
+    #[getter]
+    fn in_flight_requests(&self) -> PyResult<usize> {
+        let state = self.0.state
+            .lock()
+            .map_err(Error::from)?;
+
+        Ok(state.in_flight)
+    }
+
+    /// :class:`int`: the most requests the server was ever responding to at once
+    #[getter]
+    fn max_concurrent_requests(&self) -> PyResult<usize> {
+        let state = self.0.state
+            .lock()
+            .map_err(Error::from)?;
+
+        Ok(state.max_in_flight)
+    }
+}
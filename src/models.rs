@@ -1,10 +1,49 @@
-use pyo3::prelude::*;
-use akinator_rs::models::Guess as GuessModel;
+use crate::{
+    enums::Answer,
+    error::{
+        Error,
+        Result,
+    },
+    state::PyState,
+};
 
+use std::sync::{
+    Arc,
+    Mutex,
+};
+
+use pyo3::{
+    prelude::*,
+    basic::CompareOp,
+    types::{
+        PyString,
+        PyType,
+    },
+};
+use serde::{
+    Serialize,
+    Deserialize,
+};
+
+
+/// the raw data of a guess, as returned by the akinator API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuessModel {
+    pub id: String,
+    pub name: String,
+    pub award_id: String,
+    pub flag_photo: usize,
+    #[serde(rename(serialize = "confidence"), alias = "proba")]
+    pub confidence: String,
+    pub description: String,
+    pub ranking: String,
+    pub picture_path: String,
+    pub absolute_picture_path: String,
+}
 
 /// a model class representing an akinator's guess
 /// not meant for the user to construct, but is returned in various properties and methods in the :class:`Akinator` class
-#[pyclass]
+#[pyclass(module = "akinator")]
 #[derive(Debug, Clone)]
 pub struct Guess(
     pub GuessModel,
@@ -21,6 +60,40 @@ impl Guess {
         )
     }
 
+    fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (PyObject,))> {
+        let from_dict = py.get_type::<Self>()
+            .getattr("from_dict")?;
+
+        Ok((from_dict, (self.to_dict(py)?,)))
+    }
+
+    /// Converts this guess into a :class:`dict`
+    ///
+    /// Returns
+    /// -------
+    /// :class:`dict`
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.0.to_py(py)
+    }
+
+    /// a classmethod to rebuild a :class:`Guess` from a dict previously returned by ``to_dict``
+    ///
+    /// Parameters
+    /// ----------
+    /// data : :class:`dict`
+    ///     the guess's data
+    ///
+    /// Raises
+    /// ------
+    /// :class:`ValueError`
+    ///     the given data could not be parsed (invalid, missing fields etc.)
+    #[classmethod]
+    #[pyo3(text_signature = "(self, data)")]
+    fn from_dict(_cls: &PyType, data: &PyAny) -> PyResult<Self> {
+        GuessModel::from_py(data)
+            .map(Self)
+    }
+
     /// :class:`str`: the unique ID of the specific guess's entity
     #[getter]
     const fn id(&self) -> &String {
@@ -77,4 +150,450 @@ impl Guess {
     const fn absolute_picture_path(&self) -> &String {
         &self.0.absolute_picture_path
     }
-}
\ No newline at end of file
+}
+
+
+/// the raw data of a question asked by the akinator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionModel {
+    pub text: String,
+    pub step: usize,
+    pub progression: f32,
+    pub id: Option<String>,
+    pub answers: Vec<String>,
+}
+
+/// a model class representing a question asked by the akinator
+/// not meant for the user to construct, but is returned by ``start_game``, ``answer`` and ``back``
+/// and yielded when iterating over ``questions()`` of the :class:`Akinator` class
+///
+/// ``str(question)`` is the text of the question, which it also compares equal to,
+/// so it can be used in place of the plain :class:`str` these methods used to return
+///
+/// calling ``answer`` on it submits the answer once the next question is requested from the ``questions()`` iterator
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+pub struct Question {
+    pub model: QuestionModel,
+    answer: Arc<Mutex<Option<Answer>>>,
+}
+
+impl From<QuestionModel> for Question {
+    fn from(model: QuestionModel) -> Self {
+        Self {
+            model,
+            answer: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl Question {
+    /// takes the answer given with ``answer``, if any
+    pub(crate) fn take_answer(&self) -> Result<Option<Answer>> {
+        Ok(self.answer.lock()?.take())
+    }
+}
+
+#[pymethods]
+impl Question {
+    fn __repr__(&self) -> String {
+        format!(
+            "<Question step={} progression={} text=\"{}\">",
+            self.model.step,
+            self.model.progression,
+            self.model.text,
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.model.text.clone()
+    }
+
+    fn __richcmp__(&self, other: &PyAny, op: CompareOp) -> PyObject {
+        let py = other.py();
+
+        let equal = if let Ok(other) = other.extract::<PyRef<'_, Self>>() {
+            self.model.text == other.model.text && self.model.step == other.model.step
+        } else if let Ok(text) = other.extract::<&str>() {
+            self.model.text == text
+        } else {
+            return py.NotImplemented();
+        };
+
+        match op {
+            CompareOp::Eq => equal.into_py(py),
+            CompareOp::Ne => (!equal).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    /// hashed as its text, to be found in the sets and dicts keyed by the plain :class:`str`
+    fn __hash__(&self, py: Python<'_>) -> PyResult<isize> {
+        PyString::new(py, &self.model.text)
+            .hash()
+    }
+
+    fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (PyObject,))> {
+        let from_dict = py.get_type::<Self>()
+            .getattr("from_dict")?;
+
+        Ok((from_dict, (self.to_dict(py)?,)))
+    }
+
+    /// Converts this question into a :class:`dict`,
+    /// leaving out any answer given to it with ``answer`` that has not been sent yet
+    ///
+    /// Returns
+    /// -------
+    /// :class:`dict`
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.model.to_py(py)
+    }
+
+    /// a classmethod to rebuild a :class:`Question` from a dict previously returned by ``to_dict``
+    ///
+    /// Parameters
+    /// ----------
+    /// data : :class:`dict`
+    ///     the question's data
+    ///
+    /// Raises
+    /// ------
+    /// :class:`ValueError`
+    ///     the given data could not be parsed (invalid, missing fields etc.)
+    #[classmethod]
+    #[pyo3(text_signature = "(self, data)")]
+    fn from_dict(_cls: &PyType, data: &PyAny) -> PyResult<Self> {
+        QuestionModel::from_py(data)
+            .map(Self::from)
+    }
+
+    /// Answers this question with the provided ``answer``,
+    /// which is sent to the akinator when the ``questions()`` iterator advances to the next question
+    ///
+    /// Parameters
+    /// ----------
+    /// answer : :class:`Answer`
+    ///     the answer to the question
+    #[pyo3(text_signature = "(self, answer)")]
+    fn answer(&self, answer: Answer) -> PyResult<()> {
+        *self.answer
+            .lock()
+            .map_err(Error::from)? = Some(answer);
+
+        Ok(())
+    }
+
+    /// :class:`str`: the text of the question
+    #[getter]
+    const fn text(&self) -> &String {
+        &self.model.text
+    }
+
+    /// :class:`int`: the step the question was asked at
+    #[getter]
+    const fn step(&self) -> usize {
+        self.model.step
+    }
+
+    /// :class:`float`: the progression of the akinator when the question was asked
+    #[getter]
+    const fn progression(&self) -> f32 {
+        self.model.progression
+    }
+
+    /// Optional[:class:`str`]: the ID of the question on the akinator servers, if they sent one
+    #[getter]
+    fn id(&self) -> Option<String> {
+        self.model.id.clone()
+    }
+
+    /// List[:class:`str`]: the labels of the possible answers in the language of the game,
+    /// in the same order as the :class:`Answer` variants
+    #[getter]
+    fn answers(&self) -> Vec<String> {
+        self.model.answers.clone()
+    }
+}
+
+
+/// the raw data of a single answered question in the game's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntryModel {
+    pub step: usize,
+    pub question: String,
+    pub answer: Answer,
+    pub progression_before: f32,
+    pub progression_after: f32,
+    pub timestamp: f64,
+}
+
+/// a model class representing a question the akinator asked and the answer it was given
+/// not meant for the user to construct, but is returned in the ``history`` property of the :class:`Akinator` class
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+pub struct HistoryEntry(
+    pub HistoryEntryModel,
+);
+
+#[pymethods]
+impl HistoryEntry {
+    fn __repr__(&self) -> String {
+        format!(
+            "<HistoryEntry step={} question=\"{}\" answer=\"{:?}\">",
+            self.step(),
+            self.question(),
+            self.answer(),
+        )
+    }
+
+    fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (PyObject,))> {
+        let from_dict = py.get_type::<Self>()
+            .getattr("from_dict")?;
+
+        Ok((from_dict, (self.to_dict(py)?,)))
+    }
+
+    /// Converts this entry into a :class:`dict`
+    ///
+    /// Returns
+    /// -------
+    /// :class:`dict`
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.0.to_py(py)
+    }
+
+    /// a classmethod to rebuild a :class:`HistoryEntry` from a dict previously returned by ``to_dict``
+    ///
+    /// Parameters
+    /// ----------
+    /// data : :class:`dict`
+    ///     the entry's data
+    ///
+    /// Raises
+    /// ------
+    /// :class:`ValueError`
+    ///     the given data could not be parsed (invalid, missing fields etc.)
+    #[classmethod]
+    #[pyo3(text_signature = "(self, data)")]
+    fn from_dict(_cls: &PyType, data: &PyAny) -> PyResult<Self> {
+        HistoryEntryModel::from_py(data)
+            .map(Self)
+    }
+
+    /// :class:`int`: the step the question was asked at
+    #[getter]
+    const fn step(&self) -> usize {
+        self.0.step
+    }
+
+    /// :class:`str`: the question that was asked
+    #[getter]
+    const fn question(&self) -> &String {
+        &self.0.question
+    }
+
+    /// :class:`Answer`: the answer given to the question
+    #[getter]
+    const fn answer(&self) -> Answer {
+        self.0.answer
+    }
+
+    /// :class:`float`: the progression of the akinator before the question was answered
+    #[getter]
+    const fn progression_before(&self) -> f32 {
+        self.0.progression_before
+    }
+
+    /// :class:`float`: the progression of the akinator after the question was answered
+    #[getter]
+    const fn progression_after(&self) -> f32 {
+        self.0.progression_after
+    }
+
+    /// :class:`float`: the unix timestamp of when the question was answered
+    #[getter]
+    const fn timestamp(&self) -> f64 {
+        self.0.timestamp
+    }
+}
+
+
+/// the raw data of the outcome of a game whose guess was confirmed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameResultModel {
+    pub guess: GuessModel,
+    pub step: usize,
+    pub times_played: Option<usize>,
+    pub timestamp: f64,
+}
+
+/// a model class representing the outcome of a game, once the akinator's guess has been confirmed
+/// not meant for the user to construct, but is returned by the ``confirm_guess`` method of the :class:`Akinator` class
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+pub struct GameResult(
+    pub GameResultModel,
+);
+
+#[pymethods]
+impl GameResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "<GameResult guess=\"{}\" step={} times_played={}>",
+            self.0.guess.name,
+            self.step(),
+            self.times_played().map_or_else(|| "None".to_string(), |times| times.to_string()),
+        )
+    }
+
+    fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (PyObject,))> {
+        let from_dict = py.get_type::<Self>()
+            .getattr("from_dict")?;
+
+        Ok((from_dict, (self.to_dict(py)?,)))
+    }
+
+    /// Converts this result into a :class:`dict`
+    ///
+    /// Returns
+    /// -------
+    /// :class:`dict`
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.0.to_py(py)
+    }
+
+    /// a classmethod to rebuild a :class:`GameResult` from a dict previously returned by ``to_dict``
+    ///
+    /// Parameters
+    /// ----------
+    /// data : :class:`dict`
+    ///     the result's data
+    ///
+    /// Raises
+    /// ------
+    /// :class:`ValueError`
+    ///     the given data could not be parsed (invalid, missing fields etc.)
+    #[classmethod]
+    #[pyo3(text_signature = "(self, data)")]
+    fn from_dict(_cls: &PyType, data: &PyAny) -> PyResult<Self> {
+        GameResultModel::from_py(data)
+            .map(Self)
+    }
+
+    /// :class:`Guess`: the guess that was confirmed to be correct
+    #[getter]
+    fn guess(&self) -> Guess {
+        Guess(self.0.guess.clone())
+    }
+
+    /// :class:`int`: the step the game was won at
+    #[getter]
+    const fn step(&self) -> usize {
+        self.0.step
+    }
+
+    /// Optional[:class:`int`]: how many times the guess's entity has been played, as reported by the akinator servers
+    #[getter]
+    const fn times_played(&self) -> Option<usize> {
+        self.0.times_played
+    }
+
+    /// :class:`float`: the unix timestamp of when the guess was confirmed
+    #[getter]
+    const fn timestamp(&self) -> f64 {
+        self.0.timestamp
+    }
+}
+
+
+/// what answering a question led to, once a guess policy has been evaluated
+#[derive(Debug, Clone)]
+pub enum AnswerOutcome {
+    Question(Option<QuestionModel>),
+    Guess(GuessModel),
+}
+
+/// a model class representing the result of answering a question while evaluating a :class:`GuessPolicy`,
+/// either the next question or the guess the policy decided to propose, as told by ``kind``
+/// not meant for the user to construct, but is returned by the ``answer`` method of the :class:`Akinator` class when a :class:`GuessPolicy` is set
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+pub struct AnswerResult(
+    pub AnswerOutcome,
+);
+
+#[pymethods]
+impl AnswerResult {
+    fn __repr__(&self) -> String {
+        match &self.0 {
+            AnswerOutcome::Question(question) => format!(
+                "<AnswerResult kind=\"question\" question={:?}>",
+                question.as_ref().map(|question| &question.text),
+            ),
+            AnswerOutcome::Guess(guess) => format!("<AnswerResult kind=\"guess\" guess=\"{}\">", guess.name),
+        }
+    }
+
+    fn __str__(&self) -> String {
+        match &self.0 {
+            AnswerOutcome::Question(question) => question
+                .as_ref()
+                .map(|question| question.text.clone())
+                .unwrap_or_default(),
+            AnswerOutcome::Guess(guess) => guess.name.clone(),
+        }
+    }
+
+    /// :class:`str`: either ``"question"`` or ``"guess"``, telling which of ``question`` and ``guess`` is set
+    #[getter]
+    const fn kind(&self) -> &'static str {
+        match self.0 {
+            AnswerOutcome::Question(_) => "question",
+            AnswerOutcome::Guess(_) => "guess",
+        }
+    }
+
+    /// :class:`bool`: whether the policy decided to propose a guess
+    #[getter]
+    const fn is_guess(&self) -> bool {
+        matches!(self.0, AnswerOutcome::Guess(_))
+    }
+
+    /// Optional[:class:`Question`]: the next question, if the game goes on
+    #[getter]
+    fn question(&self) -> Option<Question> {
+        match &self.0 {
+            AnswerOutcome::Question(question) => question.clone().map(Question::from),
+            AnswerOutcome::Guess(_) => None,
+        }
+    }
+
+    /// Optional[:class:`Guess`]: the proposed guess, to be confirmed with ``confirm_guess`` or rejected with ``reject_guess``
+    #[getter]
+    fn guess(&self) -> Option<Guess> {
+        match &self.0 {
+            AnswerOutcome::Question(_) => None,
+            AnswerOutcome::Guess(guess) => Some(Guess(guess.clone())),
+        }
+    }
+}
+
+/// what ``answer`` returns, depending on whether a guess policy was evaluated:
+/// the next question as is, or an :class:`AnswerResult` telling it apart from a proposed guess
+#[derive(Debug, Clone)]
+pub enum AnswerReply {
+    Question(Option<QuestionModel>),
+    Evaluated(AnswerOutcome),
+}
+
+impl IntoPy<PyObject> for AnswerReply {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Self::Question(question) => question
+                .map(Question::from)
+                .into_py(py),
+            Self::Evaluated(outcome) => AnswerResult(outcome).into_py(py),
+        }
+    }
+}
@@ -0,0 +1,512 @@
+//! A pool of async games looked up by key, such as one game per user of a chat bot
+
+use crate::{
+    async_akinator::AsyncAkinator,
+    enums::{
+        Theme,
+        Language,
+    },
+    error::{
+        Error,
+        Result,
+    },
+    game::Game,
+    mock::MockServer,
+    rate_limit::RateLimiter,
+    retry::RetryPolicy,
+    shared::SharedGame,
+    transport::{
+        HttpConfig,
+        HttpTransport,
+        timeout_from_secs,
+    },
+};
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use pyo3::{
+    prelude::*,
+    exceptions::PyValueError,
+};
+
+/// the key a session is stored under, either an :class:`int` (such as a user ID) or a :class:`str`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, FromPyObject)]
+enum SessionKey {
+    Int(i64),
+    Str(String),
+}
+
+impl IntoPy<PyObject> for SessionKey {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Self::Int(key) => key.into_py(py),
+            Self::Str(key) => key.into_py(py),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Session {
+    akinator: Py<AsyncAkinator>,
+    /// when the game was last looked up or created
+    last_used: Instant,
+}
+
+impl Session {
+    /// how long the game has gone without being looked up or making a request, whichever happened last
+    ///
+    /// measured with the monotonic clock only, so that changes to the system clock cannot evict games early or keep them forever
+    fn idle(&self, py: Python<'_>, now: Instant) -> Result<Duration> {
+        let requested = self.akinator
+            .borrow(py)
+            .0
+            .read(Game::last_request)?;
+
+        let last_active = requested.map_or(self.last_used, |requested| requested.max(self.last_used));
+
+        Ok(now.saturating_duration_since(last_active))
+    }
+
+    /// marks the game as closed, once any operation in flight on it has completed
+    fn close(&self, py: Python<'_>) -> Result<()> {
+        self.akinator
+            .borrow(py)
+            .0
+            .update(|game| {
+                // the games of a pool share its HTTP client, so closing one of them cannot fail
+                let _ = game.close();
+            })
+    }
+}
+
+/// Represents a pool of :class:`AsyncAkinator` games, each stored under a key such as the ID of a user
+///
+/// every game of the pool shares a single HTTP client (and so its connections),
+/// and games are closed and evicted from the pool once they have been idle (neither looked up nor making requests) for longer than ``ttl``,
+/// or when the pool is full and room has to be made for a new game
+///
+/// Parameters
+/// ----------
+/// ttl : Optional[:class:`float`]
+///     the number of seconds a game can go without being looked up or making a request before it is evicted,
+///     defaults to ``None`` (games are never evicted for being idle)
+/// max_sessions : Optional[:class:`int`]
+///     the maximum number of games in the pool at once,
+///     the least recently used game is evicted to make room for a new one once it is reached,
+///     defaults to ``None`` (no limit)
+/// theme : Optional[:class:`Theme`]
+///     the default theme of the games created by the pool,
+///     defaults to ``Characters``
+/// language : Optional[:class:`Language`]
+///     the default language of the games created by the pool,
+///     defaults to ``English``
+/// child_mode : Optional[:class:`bool`]
+///     whether the games created by the pool are in child mode by default,
+///     defaults to ``False``
+/// transport : Optional[:class:`MockServer`]
+///     a local stand-in to send the requests of every game to instead of the akinator servers,
+///     defaults to ``None`` (the real akinator servers)
+/// proxy : Optional[:class:`str`]
+///     the url of a proxy to send all requests through,
+///     defaults to ``None``
+/// connect_timeout : Optional[:class:`float`]
+///     the timeout in seconds for establishing a connection to the akinator servers,
+///     defaults to ``None`` (no timeout)
+/// request_timeout : Optional[:class:`float`]
+///     the timeout in seconds for each request as a whole,
+///     defaults to ``None`` (no timeout)
+/// user_agent : Optional[:class:`str`]
+///     the ``User-Agent`` header to send with every request,
+///     defaults to a desktop browser user agent
+/// headers : Optional[Dict[:class:`str`, :class:`str`]]
+///     extra headers to send with every request,
+///     defaults to ``None``
+/// retry : Optional[:class:`RetryPolicy`]
+///     the policy the games of the pool retry requests that failed with a transient error with,
+///     defaults to ``None`` (no retries)
+/// rate_limiter : Optional[:class:`RateLimiter`]
+///     a rate limiter every request of every game of the pool waits on, limiting the requests of the pool as a whole,
+///     defaults to ``None`` (only the global rate limiter set with ``set_rate_limiter`` applies, if any)
+#[pyclass(module = "akinator")]
+#[derive(Debug)]
+#[pyo3(text_signature = "(*, ttl = None, max_sessions = None, theme = None, language = None, child_mode = None, transport = None, proxy = None, connect_timeout = None, request_timeout = None, user_agent = None, headers = None, retry = None, rate_limiter = None)")]
+pub struct AkinatorPool {
+    http: Arc<HttpTransport>,
+    transport: Option<MockServer>,
+    retry: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    theme: Theme,
+    language: Language,
+    child_mode: bool,
+    ttl: Option<Duration>,
+    max_sessions: Option<usize>,
+    sessions: Mutex<HashMap<SessionKey, Session>>,
+}
+
+impl AkinatorPool {
+    fn is_expired(&self, py: Python<'_>, session: &Session, now: Instant) -> Result<bool> {
+        match self.ttl {
+            Some(ttl) => Ok(session.idle(py, now)? > ttl),
+            None => Ok(false),
+        }
+    }
+
+    /// the keys of every game that has not expired
+    fn active_keys(&self, py: Python<'_>) -> Result<Vec<SessionKey>> {
+        let now = Instant::now();
+        let sessions = self.sessions.lock()?;
+        let mut keys = Vec::new();
+
+        for (key, session) in sessions.iter() {
+            if !self.is_expired(py, session, now)? {
+                keys.push(key.clone());
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// closes and removes every game that has been idle for longer than the ttl, returning how many there were
+    fn evict_expired_sessions(&self, py: Python<'_>, sessions: &mut HashMap<SessionKey, Session>) -> Result<usize> {
+        let now = Instant::now();
+        let mut expired = Vec::new();
+
+        for (key, session) in sessions.iter() {
+            if self.is_expired(py, session, now)? {
+                expired.push(key.clone());
+            }
+        }
+
+        for key in &expired {
+            if let Some(session) = sessions.remove(key) {
+                session.close(py)?;
+            }
+        }
+
+        Ok(expired.len())
+    }
+
+    /// closes and removes the least recently used games until there is room for a new one
+    fn make_room(&self, py: Python<'_>, sessions: &mut HashMap<SessionKey, Session>) -> Result<()> {
+        let max_sessions = match self.max_sessions {
+            Some(max_sessions) => max_sessions,
+            None => return Ok(()),
+        };
+
+        while sessions.len() >= max_sessions {
+            let now = Instant::now();
+            let mut idle = Vec::with_capacity(sessions.len());
+
+            for (key, session) in sessions.iter() {
+                idle.push((session.idle(py, now)?, key.clone()));
+            }
+
+            let oldest = idle.into_iter()
+                .max_by_key(|(idle, _)| *idle)
+                .map(|(_, key)| key);
+
+            match oldest.and_then(|key| sessions.remove(&key)) {
+                Some(session) => session.close(py)?,
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn new_game(&self, theme: Option<Theme>, language: Option<Language>, child_mode: Option<bool>) -> Game {
+        let mut game = Game::with_shared_http(self.http.clone())
+            .with_retry(self.retry.clone())
+            .with_rate_limiter(self.rate_limiter.clone())
+            .with_theme(theme.unwrap_or(self.theme))
+            .with_language(language.unwrap_or(self.language));
+
+        if child_mode.unwrap_or(self.child_mode) {
+            game = game.with_child_mode();
+        }
+
+        if let Some(transport) = &self.transport {
+            game = game.with_mock_server(transport.clone());
+        }
+
+        game
+    }
+}
+
+#[pymethods]
+impl AkinatorPool {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[args("*", ttl, max_sessions, theme, language, child_mode, transport, proxy, connect_timeout, request_timeout, user_agent, headers, retry, rate_limiter)]
+    fn constructor(
+        ttl: Option<f64>,
+        max_sessions: Option<usize>,
+        theme: Option<Theme>,
+        language: Option<Language>,
+        child_mode: Option<bool>,
+        transport: Option<MockServer>,
+        proxy: Option<String>,
+        connect_timeout: Option<f64>,
+        request_timeout: Option<f64>,
+        user_agent: Option<String>,
+        headers: Option<HashMap<String, String>>,
+        retry: Option<RetryPolicy>,
+        rate_limiter: Option<RateLimiter>,
+    ) -> PyResult<Self> {
+        if max_sessions == Some(0) {
+            return Err(PyValueError::new_err("`max_sessions` must be at least 1"));
+        }
+
+        let http = HttpTransport::new(HttpConfig {
+            proxy,
+            connect_timeout: connect_timeout.map(timeout_from_secs).transpose()?,
+            request_timeout: request_timeout.map(timeout_from_secs).transpose()?,
+            user_agent,
+            headers: headers.unwrap_or_default(),
+        })?;
+
+        Ok(Self {
+            http: Arc::new(http),
+            transport,
+            retry,
+            rate_limiter,
+            theme: theme.unwrap_or(Theme::Characters),
+            language: language.unwrap_or(Language::English),
+            child_mode: child_mode.unwrap_or(false),
+            ttl: ttl.map(timeout_from_secs).transpose()?,
+            max_sessions,
+            sessions: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "<AkinatorPool active_sessions={} ttl={:?} max_sessions={:?}>",
+            self.active_sessions(py)?,
+            self.ttl(),
+            self.max_sessions,
+        ))
+    }
+
+    fn __len__(&self, py: Python<'_>) -> PyResult<usize> {
+        self.active_sessions(py)
+    }
+
+    fn __contains__(&self, py: Python<'_>, key: SessionKey) -> PyResult<bool> {
+        let sessions = self.sessions.lock()
+            .map_err(Error::from)?;
+
+        match sessions.get(&key) {
+            Some(session) => Ok(!self.is_expired(py, session, Instant::now())?),
+            None => Ok(false),
+        }
+    }
+
+    /// Looks up the game stored under ``key``, marking it as used
+    ///
+    /// Parameters
+    /// ----------
+    /// key : Union[:class:`int`, :class:`str`]
+    ///     the key the game is stored under
+    ///
+    /// Returns
+    /// -------
+    /// Optional[:class:`AsyncAkinator`]
+    ///     the game, or ``None`` if there is none (or it has been evicted)
+    #[pyo3(text_signature = "(self, key)")]
+    fn get(&self, py: Python<'_>, key: SessionKey) -> PyResult<Option<Py<AsyncAkinator>>> {
+        let mut sessions = self.sessions.lock()
+            .map_err(Error::from)?;
+
+        self.evict_expired_sessions(py, &mut sessions)?;
+
+        Ok(sessions.get_mut(&key)
+            .map(|session| {
+                session.last_used = Instant::now();
+                session.akinator.clone_ref(py)
+            }))
+    }
+
+    /// Creates a new game stored under ``key``, closing and replacing any game already stored under it
+    ///
+    /// the game still has to be started with ``start_game``
+    ///
+    /// Parameters
+    /// ----------
+    /// key : Union[:class:`int`, :class:`str`]
+    ///     the key to store the game under
+    /// theme : Optional[:class:`Theme`]
+    ///     the theme of the game, defaults to the theme of the pool
+    /// language : Optional[:class:`Language`]
+    ///     the language of the game, defaults to the language of the pool
+    /// child_mode : Optional[:class:`bool`]
+    ///     whether the game is in child mode, defaults to that of the pool
+    ///
+    /// Returns
+    /// -------
+    /// :class:`AsyncAkinator`
+    #[args(key, "*", theme, language, child_mode)]
+    #[pyo3(text_signature = "(self, key, *, theme = None, language = None, child_mode = None)")]
+    fn create(
+        &self,
+        py: Python<'_>,
+        key: SessionKey,
+        theme: Option<Theme>,
+        language: Option<Language>,
+        child_mode: Option<bool>,
+    ) -> PyResult<Py<AsyncAkinator>> {
+        let mut sessions = self.sessions.lock()
+            .map_err(Error::from)?;
+
+        if let Some(session) = sessions.remove(&key) {
+            session.close(py)?;
+        }
+
+        self.evict_expired_sessions(py, &mut sessions)?;
+        self.make_room(py, &mut sessions)?;
+
+        let game = self.new_game(theme, language, child_mode);
+        let akinator = Py::new(py, AsyncAkinator(Arc::new(SharedGame::new(game))))?;
+
+        sessions.insert(key, Session {
+            akinator: akinator.clone_ref(py),
+            last_used: Instant::now(),
+        });
+
+        Ok(akinator)
+    }
+
+    /// Looks up the game stored under ``key`` like ``get``,
+    /// creating a new one like ``create`` if there is none
+    ///
+    /// Parameters
+    /// ----------
+    /// key : Union[:class:`int`, :class:`str`]
+    ///     the key the game is stored under
+    /// theme : Optional[:class:`Theme`]
+    ///     the theme of the game if it is created, defaults to the theme of the pool
+    /// language : Optional[:class:`Language`]
+    ///     the language of the game if it is created, defaults to the language of the pool
+    /// child_mode : Optional[:class:`bool`]
+    ///     whether the game is in child mode if it is created, defaults to that of the pool
+    ///
+    /// Returns
+    /// -------
+    /// :class:`AsyncAkinator`
+    #[args(key, "*", theme, language, child_mode)]
+    #[pyo3(text_signature = "(self, key, *, theme = None, language = None, child_mode = None)")]
+    fn get_or_create(
+        &self,
+        py: Python<'_>,
+        key: SessionKey,
+        theme: Option<Theme>,
+        language: Option<Language>,
+        child_mode: Option<bool>,
+    ) -> PyResult<Py<AsyncAkinator>> {
+        match self.get(py, key.clone())? {
+            Some(akinator) => Ok(akinator),
+            None => self.create(py, key, theme, language, child_mode),
+        }
+    }
+
+    /// Closes and removes the game stored under ``key``
+    ///
+    /// Parameters
+    /// ----------
+    /// key : Union[:class:`int`, :class:`str`]
+    ///     the key the game is stored under
+    ///
+    /// Returns
+    /// -------
+    /// :class:`bool`
+    ///     whether there was a game stored under ``key``
+    #[pyo3(text_signature = "(self, key)")]
+    fn remove(&self, py: Python<'_>, key: SessionKey) -> PyResult<bool> {
+        let session = self.sessions.lock()
+            .map_err(Error::from)?
+            .remove(&key);
+
+        match session {
+            Some(session) => {
+                session.close(py)?;
+                Ok(true)
+            },
+            None => Ok(false),
+        }
+    }
+
+    /// Closes and removes every game that has been idle for longer than the ``ttl`` of the pool,
+    /// which otherwise happens whenever a game is looked up or created
+    ///
+    /// Returns
+    /// -------
+    /// :class:`int`
+    ///     the number of games that were evicted
+    fn evict_expired(&self, py: Python<'_>) -> PyResult<usize> {
+        let mut sessions = self.sessions.lock()
+            .map_err(Error::from)?;
+
+        self.evict_expired_sessions(py, &mut sessions)
+            .map_err(PyErr::from)
+    }
+
+    /// Returns the keys of every game in the pool that has not expired
+    ///
+    /// Returns
+    /// -------
+    /// List[Union[:class:`int`, :class:`str`]]
+    fn keys(&self, py: Python<'_>) -> PyResult<Vec<SessionKey>> {
+        self.active_keys(py)
+            .map_err(PyErr::from)
+    }
+
+    /// Closes every game in the pool, along with the HTTP client they share,
+    /// any further calls to the games raise :class:`SessionClosed`
+    fn close(&self, py: Python<'_>) -> PyResult<()> {
+        let sessions = std::mem::take(
+            &mut *self.sessions.lock()
+                .map_err(Error::from)?
+        );
+
+        for session in sessions.values() {
+            session.close(py)?;
+        }
+
+        self.http.close()
+            .map_err(PyErr::from)
+    }
+
+    /// :class:`int`: the number of games in the pool that have not expired
+    #[getter]
+    fn active_sessions(&self, py: Python<'_>) -> PyResult<usize> {
+        Ok(self.active_keys(py)?.len())
+    }
+
+    /// Optional[:class:`float`]: the number of seconds a game can go without being looked up or making a request before it is evicted
+    #[getter]
+    fn ttl(&self) -> Option<f64> {
+        self.ttl.map(|ttl| ttl.as_secs_f64())
+    }
+
+    /// Optional[:class:`RateLimiter`]: the rate limiter every request of every game of the pool waits on, if any
+    #[getter]
+    fn rate_limiter(&self) -> Option<RateLimiter> {
+        self.rate_limiter.clone()
+    }
+
+    /// Optional[:class:`int`]: the maximum number of games in the pool at once
+    #[getter]
+    const fn max_sessions(&self) -> Option<usize> {
+        self.max_sessions
+    }
+}
@@ -0,0 +1,39 @@
+//! The iteration over the questions of a game with ``questions()``
+
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    game::Game,
+    models::Question,
+};
+
+use akinator_rs::error::Error as AkiError;
+
+/// advances a game being iterated over, returning the next question to yield
+/// or ``None`` once the iteration is over
+///
+/// the game is started if it has not been yet, and the answer given to the ``previous`` question (if any) is submitted,
+/// unless the game has already moved on from it (i.e. by answering or going back directly)
+pub(crate) async fn next_question(game: &mut Game, previous: Option<&Question>, threshold: f32) -> Result<Option<Question>> {
+    let result = match previous {
+        None if game.current_question.is_none() => game.start().await.map(drop),
+        Some(previous) if previous.model.step == game.step => match previous.take_answer()? {
+            Some(answer) => game.answer(answer).await.map(drop),
+            None => return Err(Error::UnansweredQuestion),
+        },
+        _ => Ok(()),
+    };
+
+    match result {
+        Err(err) if matches!(err.inner(), Error::AkiError(AkiError::NoMoreQuestions)) => return Ok(None),
+        result => result?,
+    }
+
+    if game.progression >= threshold {
+        return Ok(None);
+    }
+
+    Ok(game.question().map(Question::from))
+}
@@ -0,0 +1,192 @@
+use crate::{
+    error::{
+        Error,
+        Result,
+    },
+    logging::{
+        log,
+        Level,
+    },
+};
+
+use std::{
+    sync::{
+        Arc,
+        Mutex,
+        RwLock,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use lazy_static::lazy_static;
+use pyo3::{
+    prelude::*,
+    exceptions::PyValueError,
+};
+
+lazy_static! {
+    /// the rate limiter every request of every game goes through, on top of any limiter of the game itself
+    static ref GLOBAL: RwLock<Option<RateLimiter>> = RwLock::new(None);
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// the tokens currently available, negative when requests are already waiting on future tokens
+    tokens: f64,
+    refilled: Instant,
+}
+
+/// A token bucket limiting the rate requests are sent to the akinator servers at
+///
+/// every request takes a token from the bucket, which is refilled at ``rate`` tokens per second up to ``burst`` tokens,
+/// requests made while the bucket is empty wait for a token to become available.
+/// The same limiter can be shared between any number of games (including an :class:`AkinatorPool`)
+/// to limit their requests as a whole, or be attached to every game at once with ``set_rate_limiter``
+///
+/// Parameters
+/// ----------
+/// rate : :class:`float`
+///     the number of requests allowed per second on average
+/// burst : Optional[:class:`int`]
+///     the number of requests that can be sent at once after a quiet period,
+///     defaults to ``1``
+///
+/// Raises
+/// ------
+/// :class:`ValueError`
+///     ``rate`` is not positive, or ``burst`` is ``0``
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+#[pyo3(text_signature = "(rate, *, burst = None)")]
+pub struct RateLimiter {
+    rate: f64,
+    burst: usize,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+impl RateLimiter {
+    /// takes a token from the bucket, returning how long to wait for before it can be used
+    fn reserve(&self) -> Result<Duration> {
+        let mut bucket = self.bucket.lock()?;
+        let now = Instant::now();
+
+        #[allow(clippy::cast_precision_loss)]
+        let burst = self.burst as f64;
+
+        bucket.tokens = (bucket.tokens + now.duration_since(bucket.refilled).as_secs_f64() * self.rate)
+            .min(burst);
+        bucket.refilled = now;
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            return Ok(Duration::ZERO);
+        }
+
+        let wait = -bucket.tokens / self.rate;
+
+        Duration::try_from_secs_f64(wait)
+            .map_err(|_| Error::ConfigError(format!("the rate limiter cannot wait {wait}s for a token at a rate of {}", self.rate)))
+    }
+
+    /// waits until a request can be sent without exceeding the rate
+    pub async fn acquire(&self) -> Result<()> {
+        let delay = self.reserve()?;
+
+        if !delay.is_zero() {
+            log!(
+                Level::Debug,
+                format!("waiting {:.2}s for the rate limiter", delay.as_secs_f64()),
+                delay = delay.as_secs_f64(),
+            );
+
+            tokio::time::sleep(delay).await;
+        }
+
+        Ok(())
+    }
+
+    /// the rate limiter set with ``set_rate_limiter``, if any
+    pub fn global() -> Result<Option<Self>> {
+        Ok(GLOBAL.read()?.clone())
+    }
+}
+
+#[pymethods]
+impl RateLimiter {
+    #[new]
+    #[args(rate, "*", burst)]
+    fn constructor(rate: f64, burst: Option<usize>) -> PyResult<Self> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(PyValueError::new_err("`rate` must be a positive number"));
+        }
+
+        let burst = burst.unwrap_or(1);
+
+        if burst == 0 {
+            return Err(PyValueError::new_err("`burst` must be at least 1"));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let tokens = burst as f64;
+
+        Ok(Self {
+            rate,
+            burst,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens,
+                refilled: Instant::now(),
+            })),
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<RateLimiter rate={} burst={}>",
+            self.rate,
+            self.burst,
+        )
+    }
+
+    /// :class:`float`: the number of requests allowed per second on average
+    #[getter]
+    const fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// :class:`int`: the number of requests that can be sent at once after a quiet period
+    #[getter]
+    const fn burst(&self) -> usize {
+        self.burst
+    }
+}
+
+/// Sets the rate limiter every request of every :class:`Akinator`, :class:`AsyncAkinator` and :class:`AkinatorPool` game goes through,
+/// on top of any rate limiter of the game itself
+///
+/// Parameters
+/// ----------
+/// limiter : Optional[:class:`RateLimiter`]
+///     the rate limiter to use, or ``None`` to stop limiting every game
+#[pyfunction]
+#[pyo3(text_signature = "(limiter)")]
+pub fn set_rate_limiter(limiter: Option<RateLimiter>) -> PyResult<()> {
+    *GLOBAL.write()
+        .map_err(Error::from)? = limiter;
+
+    Ok(())
+}
+
+/// Returns the rate limiter set with ``set_rate_limiter``, if any
+///
+/// Returns
+/// -------
+/// Optional[:class:`RateLimiter`]
+#[pyfunction]
+#[pyo3(text_signature = "()")]
+pub fn get_rate_limiter() -> PyResult<Option<RateLimiter>> {
+    RateLimiter::global()
+        .map_err(PyErr::from)
+}
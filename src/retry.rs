@@ -0,0 +1,238 @@
+use crate::{
+    error::Result,
+    logging::{
+        log,
+        Level,
+    },
+    transport::timeout_from_secs,
+};
+
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{
+        BuildHasher,
+        Hasher,
+    },
+    time::Duration,
+};
+
+use pyo3::{
+    prelude::*,
+    exceptions::PyValueError,
+};
+
+/// the error kinds that a request can be retried on
+const RETRYABLE: [&str; 5] = [
+    "ConnectionError",
+    "TechnicalError",
+    "ServersDown",
+    "TimeoutError",
+    "RequestError",
+];
+
+const DEFAULT_RETRY_ON: [&str; 4] = [
+    "ConnectionError",
+    "TechnicalError",
+    "ServersDown",
+    "RequestError",
+];
+
+/// a random number in ``[0, 1)``, good enough for jittering delays
+fn random_fraction() -> f64 {
+    let bits = RandomState::new()
+        .build_hasher()
+        .finish();
+
+    #[allow(clippy::cast_precision_loss)]
+    let fraction = (bits >> 11) as f64 / (1_u64 << 53) as f64;
+
+    fraction
+}
+
+/// A policy for automatically retrying requests that failed with a transient error
+///
+/// Retried requests are re-sent with the exact same parameters (such as the current ``step``),
+/// and the game state is only updated once a request succeeds.
+/// Requests that change the state of the game (``answer`` and ``back``) are only retried
+/// when the server could not have processed the failed request,
+/// so a retried answer can never advance the game twice
+///
+/// Parameters
+/// ----------
+/// max_attempts : Optional[:class:`int`]
+///     the maximum number of times to send a request, including the first attempt,
+///     defaults to ``3``
+/// base_delay : Optional[:class:`float`]
+///     the delay in seconds before the first retry,
+///     defaults to ``0.5``
+/// max_delay : Optional[:class:`float`]
+///     the maximum delay in seconds between two attempts,
+///     defaults to ``10.0``
+/// multiplier : Optional[:class:`float`]
+///     the factor the delay grows by after each retry,
+///     defaults to ``2.0``
+/// jitter : Optional[:class:`bool`]
+///     when set to ``True``, each delay is randomized between ``0`` and its full value,
+///     defaults to ``True``
+/// retry_on : Optional[List[:class:`str`]]
+///     the names of the errors to retry on, any of
+///     ``"ConnectionError"``, ``"TechnicalError"``, ``"ServersDown"``, ``"TimeoutError"`` or ``"RequestError"``,
+///     defaults to all of them except ``"TimeoutError"``
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+#[pyo3(text_signature = "(*, max_attempts = None, base_delay = None, max_delay = None, multiplier = None, jitter = None, retry_on = None)")]
+pub struct RetryPolicy {
+    max_attempts: usize,
+    base_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    jitter: bool,
+    retry_on: Vec<String>,
+}
+
+impl RetryPolicy {
+    /// the delay to wait for before the given retry (starting from ``1``)
+    fn delay(&self, retry: usize) -> Duration {
+        let exponent = i32::try_from(retry.saturating_sub(1))
+            .unwrap_or(i32::MAX);
+        let delay = (self.base_delay.as_secs_f64() * self.multiplier.powi(exponent))
+            .min(self.max_delay.as_secs_f64());
+
+        let delay = if self.jitter {
+            delay * random_fraction()
+        } else {
+            delay
+        };
+
+        Duration::try_from_secs_f64(delay)
+            .unwrap_or(self.max_delay)
+    }
+
+    /// runs ``operation`` until it succeeds, fails with an error that should not be retried,
+    /// or the maximum number of attempts is reached
+    ///
+    /// ``idempotent`` indicates whether the operation can be safely repeated
+    /// even after the server may have already processed it
+    pub async fn run<T, F, Fut>(&self, idempotent: bool, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match operation().await {
+                Err(err) if attempt < self.max_attempts
+                    && self.retry_on.iter().any(|kind| kind == err.kind())
+                    && (idempotent || err.is_unprocessed()) =>
+                {
+                    let delay = self.delay(attempt);
+
+                    log!(
+                        Level::Info,
+                        format!(
+                            "retrying a request that failed with {} in {:.2}s (attempt {} of {})",
+                            err.kind(),
+                            delay.as_secs_f64(),
+                            attempt + 1,
+                            self.max_attempts,
+                        ),
+                        error_kind = err.kind(),
+                        attempt = attempt + 1,
+                        delay = delay.as_secs_f64(),
+                    );
+
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                },
+                result => return result,
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl RetryPolicy {
+    #[new]
+    #[args("*", max_attempts, base_delay, max_delay, multiplier, jitter, retry_on)]
+    fn constructor(
+        max_attempts: Option<usize>,
+        base_delay: Option<f64>,
+        max_delay: Option<f64>,
+        multiplier: Option<f64>,
+        jitter: Option<bool>,
+        retry_on: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let retry_on = retry_on.unwrap_or_else(||
+            DEFAULT_RETRY_ON
+                .map(String::from)
+                .to_vec()
+        );
+
+        if let Some(kind) = retry_on.iter().find(|kind| !RETRYABLE.contains(&kind.as_str())) {
+            return Err(PyValueError::new_err(format!("Cannot retry on unknown error kind: {kind:?}")));
+        }
+
+        let multiplier = multiplier.unwrap_or(2.0);
+
+        if !multiplier.is_finite() || multiplier < 1.0 {
+            return Err(PyValueError::new_err("`multiplier` must be at least 1.0"));
+        }
+
+        Ok(Self {
+            max_attempts: max_attempts.unwrap_or(3).max(1),
+            base_delay: timeout_from_secs(base_delay.unwrap_or(0.5))?,
+            max_delay: timeout_from_secs(max_delay.unwrap_or(10.0))?,
+            multiplier,
+            jitter: jitter.unwrap_or(true),
+            retry_on,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<RetryPolicy max_attempts={} base_delay={} max_delay={} retry_on={:?}>",
+            self.max_attempts,
+            self.base_delay(),
+            self.max_delay(),
+            self.retry_on,
+        )
+    }
+
+    /// :class:`int`: the maximum number of times to send a request, including the first attempt
+    #[getter]
+    const fn max_attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// :class:`float`: the delay in seconds before the first retry
+    #[getter]
+    fn base_delay(&self) -> f64 {
+        self.base_delay.as_secs_f64()
+    }
+
+    /// :class:`float`: the maximum delay in seconds between two attempts
+    #[getter]
+    fn max_delay(&self) -> f64 {
+        self.max_delay.as_secs_f64()
+    }
+
+    /// :class:`float`: the factor the delay grows by after each retry
+    #[getter]
+    const fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// :class:`bool`: whether the delays are randomized
+    #[getter]
+    const fn jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// List[:class:`str`]: the names of the errors that are retried on
+    #[getter]
+    fn retry_on(&self) -> Vec<String> {
+        self.retry_on.clone()
+    }
+}
@@ -0,0 +1,163 @@
+//! The tokio runtime shared by the blocking and async classes
+//!
+//! it is built lazily the first time a game makes a request, using the options given to ``configure_runtime`` (if any),
+//! and is also registered as the runtime ``pyo3-asyncio`` runs the coroutines of the async classes on
+
+use crate::error::{
+    Error,
+    Result,
+};
+
+use lazy_static::lazy_static;
+use once_cell::sync::OnceCell;
+use tokio::runtime::{
+    Builder,
+    Runtime,
+};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Mutex,
+    },
+};
+
+use pyo3::{
+    prelude::*,
+    exceptions::{
+        PyValueError,
+        PyRuntimeError,
+    },
+};
+
+/// the options the shared runtime is built with
+#[derive(Debug, Clone)]
+struct RuntimeConfig {
+    worker_threads: Option<usize>,
+    thread_name: Option<String>,
+    enable_io_driver: bool,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            worker_threads: None,
+            thread_name: None,
+            enable_io_driver: true,
+        }
+    }
+}
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+static RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+lazy_static! {
+    static ref CONFIG: Mutex<RuntimeConfig> = Mutex::new(RuntimeConfig::default());
+}
+
+fn build_runtime() -> Result<Runtime> {
+    // held until the runtime is built, so that it cannot be configured in the meantime
+    let config = CONFIG.lock()?;
+    let mut builder = Builder::new_multi_thread();
+
+    if let Some(worker_threads) = config.worker_threads {
+        builder.worker_threads(worker_threads);
+    }
+
+    if let Some(thread_name) = &config.thread_name {
+        builder.thread_name(thread_name);
+    }
+
+    if config.enable_io_driver {
+        builder.enable_io();
+    }
+
+    let runtime = builder
+        .enable_time()
+        .build()?;
+
+    // only once it was built, so that the runtime can still be configured if building it failed
+    STARTED.store(true, Ordering::SeqCst);
+
+    Ok(runtime)
+}
+
+/// the shared runtime, building it on first use
+///
+/// fails with a :class:`RuntimeError` if it could not be built, in which case building it is tried again on next use
+pub(crate) fn runtime() -> PyResult<&'static Runtime> {
+    let runtime = RUNTIME
+        .get_or_try_init(build_runtime)
+        .map_err(|err| PyRuntimeError::new_err(format!("Failed to start the tokio runtime: {err:?}")))?;
+
+    // only succeeds the first time, as the runtime of pyo3-asyncio can only be set once
+    let _ = pyo3_asyncio::tokio::init_with_runtime(runtime);
+
+    Ok(runtime)
+}
+
+/// converts ``future`` into a python awaitable, running it on the shared runtime
+pub(crate) fn to_coro<F, T>(py: Python<'_>, future: F) -> PyResult<&PyAny>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: IntoPy<PyObject>,
+{
+    runtime()?;
+    pyo3_asyncio::tokio::future_into_py(py, future)
+}
+
+/// Configures the tokio runtime shared by all :class:`Akinator` and :class:`AsyncAkinator` games
+///
+/// must be called before any game makes its first request, as the runtime cannot be changed once it has started
+///
+/// Parameters
+/// ----------
+/// worker_threads : Optional[:class:`int`]
+///     the number of threads the runtime runs requests on,
+///     defaults to ``None`` (one per CPU core)
+/// thread_name : Optional[:class:`str`]
+///     the name given to the threads of the runtime,
+///     defaults to ``None`` (``tokio-runtime-worker``)
+/// enable_io_driver : Optional[:class:`bool`]
+///     whether to enable the IO driver of the runtime, which is required to make any HTTP requests,
+///     only set this to ``False`` when every game is played against a :class:`MockServer` or a replayed cassette,
+///     defaults to ``True``
+///
+/// Raises
+/// ------
+/// :class:`RuntimeError`
+///     the runtime has already been started
+/// :class:`ValueError`
+///     ``worker_threads`` is ``0``
+#[pyfunction("*", worker_threads = "None", thread_name = "None", enable_io_driver = "None")]
+#[pyo3(text_signature = "(*, worker_threads = None, thread_name = None, enable_io_driver = None)")]
+pub fn configure_runtime(
+    worker_threads: Option<usize>,
+    thread_name: Option<String>,
+    enable_io_driver: Option<bool>,
+) -> PyResult<()> {
+    if worker_threads == Some(0) {
+        return Err(PyValueError::new_err("`worker_threads` must be at least 1"));
+    }
+
+    let mut config = CONFIG.lock()
+        .map_err(Error::from)?;
+
+    // checked while holding the lock, so that the runtime cannot be built in the meantime
+    if STARTED.load(Ordering::SeqCst) {
+        return Err(PyRuntimeError::new_err(
+            "The runtime has already been started, `configure_runtime` must be called before any requests are made"
+        ));
+    }
+
+    *config = RuntimeConfig {
+        worker_threads,
+        thread_name,
+        enable_io_driver: enable_io_driver.unwrap_or(true),
+    };
+
+    Ok(())
+}
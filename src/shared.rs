@@ -0,0 +1,137 @@
+//! Shared access to a game driven from python coroutines
+//!
+//! operations on the game (which may wait on the network for a while) take an exclusive lock on it,
+//! while python reads a snapshot of the game taken after the last operation, so that reading a property
+//! never has to wait on an in-flight request
+
+use crate::{
+    error::Result,
+    game::Game,
+};
+
+use std::{
+    fmt,
+    ops::{
+        Deref,
+        DerefMut,
+    },
+    sync::Mutex,
+};
+
+use tokio::sync::{
+    RwLock,
+    RwLockWriteGuard,
+};
+
+/// a change made to the game from python while an operation may be in flight
+type Update = Box<dyn FnOnce(&mut Game) + Send>;
+
+struct Snapshot {
+    game: Game,
+    /// updates already applied to the snapshot, but not yet to the game itself
+    pending: Vec<Update>,
+}
+
+/// a game that can be operated on from coroutines while being read from python without blocking
+pub struct SharedGame {
+    game: RwLock<Game>,
+    snapshot: Mutex<Snapshot>,
+}
+
+impl fmt::Debug for SharedGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedGame")
+            .finish_non_exhaustive()
+    }
+}
+
+impl SharedGame {
+    pub fn new(game: Game) -> Self {
+        Self {
+            snapshot: Mutex::new(Snapshot {
+                game: game.clone(),
+                pending: Vec::new(),
+            }),
+            game: RwLock::new(game),
+        }
+    }
+
+    /// waits for exclusive access to the game to run an operation on it,
+    /// the snapshot is refreshed once the returned guard is dropped
+    pub async fn lock(&self) -> Result<GameGuard<'_>> {
+        let mut game = self.game.write().await;
+
+        for update in self.snapshot.lock()?.pending.drain(..) {
+            update(&mut game);
+        }
+
+        Ok(GameGuard {
+            shared: self,
+            game,
+        })
+    }
+
+    /// reads from the snapshot of the game, without waiting on any in-flight operation
+    pub fn read<T>(&self, read: impl FnOnce(&Game) -> T) -> Result<T> {
+        Ok(read(&self.snapshot.lock()?.game))
+    }
+
+    /// changes the game, which is visible in the snapshot immediately
+    /// but is only applied to the game itself once no operation is in flight
+    pub fn update<F>(&self, update: F) -> Result<()>
+    where
+        F: Fn(&mut Game) + Send + 'static,
+    {
+        {
+            let mut snapshot = self.snapshot.lock()?;
+
+            update(&mut snapshot.game);
+            snapshot.pending.push(Box::new(update));
+        }
+
+        if let Ok(mut game) = self.game.try_write() {
+            self.publish(&mut game)?;
+        }
+
+        Ok(())
+    }
+
+    /// applies the pending updates to ``game`` and replaces the snapshot with it
+    fn publish(&self, game: &mut Game) -> Result<()> {
+        let mut snapshot = self.snapshot.lock()?;
+
+        for update in snapshot.pending.drain(..) {
+            update(game);
+        }
+        snapshot.game = game.clone();
+
+        Ok(())
+    }
+}
+
+/// exclusive access to a [`SharedGame`], publishing a new snapshot of it when dropped
+pub struct GameGuard<'a> {
+    shared: &'a SharedGame,
+    game: RwLockWriteGuard<'a, Game>,
+}
+
+impl Deref for GameGuard<'_> {
+    type Target = Game;
+
+    fn deref(&self) -> &Game {
+        &self.game
+    }
+}
+
+impl DerefMut for GameGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+}
+
+impl Drop for GameGuard<'_> {
+    fn drop(&mut self) {
+        // a poisoned snapshot only keeps its previous state, and is reported on the next read
+        let _ = self.shared.publish(&mut self.game);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error::Error;
+
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+};
+use pyo3::{
+    prelude::*,
+    types::PyString,
+};
+
+
+/// conversions between serializable rust types and their python representations,
+/// used for the ``to_dict`` / ``to_json`` / ``from_state`` methods and for pickling
+pub(crate) trait PyState: Serialize + DeserializeOwned {
+    /// parses ``Self`` from either a JSON :class:`str` or a :class:`dict`
+    fn from_py(state: &PyAny) -> PyResult<Self> {
+        let json = if let Ok(json) = state.downcast::<PyString>() {
+            json.to_str()?.to_string()
+        } else {
+            state.py()
+                .import("json")?
+                .call_method1("dumps", (state,))?
+                .extract::<String>()?
+        };
+
+        serde_json::from_str(&json)
+            .map_err(|e| Error::from(e).into())
+    }
+
+    /// converts ``self`` into a python :class:`dict`
+    fn to_py(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let json = self.to_json()?;
+
+        py.import("json")?
+            .call_method1("loads", (json,))
+            .map(Into::into)
+    }
+
+    /// converts ``self`` into a JSON string
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(self)
+            .map_err(|e| Error::from(e).into())
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> PyState for T {}
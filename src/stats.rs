@@ -0,0 +1,147 @@
+//! Statistics about the requests made over the course of a game
+
+use crate::state::PyState;
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use serde::{
+    Serialize,
+    Deserialize,
+};
+
+/// the requests made to a single endpoint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EndpointStats {
+    pub requests: usize,
+    pub failures: usize,
+    pub latencies: Vec<f64>,
+}
+
+/// the raw statistics of a game
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsModel {
+    pub endpoints: HashMap<String, EndpointStats>,
+    pub retries: usize,
+    pub backs: usize,
+    pub progression: Vec<(usize, f32)>,
+}
+
+impl StatsModel {
+    /// records a request made to ``endpoint`` that took ``latency`` seconds
+    pub fn record_request(&mut self, endpoint: &str, latency: f64, failed: bool) {
+        let stats = self.endpoints
+            .entry(endpoint.to_string())
+            .or_default();
+
+        stats.requests += 1;
+        stats.latencies.push(latency);
+
+        if failed {
+            stats.failures += 1;
+        }
+    }
+
+    /// records the progression the game reached at ``step``
+    pub fn record_step(&mut self, step: usize, progression: f32) {
+        self.progression.push((step, progression));
+    }
+
+    fn latencies(&self) -> impl Iterator<Item = f64> + '_ {
+        self.endpoints
+            .values()
+            .flat_map(|stats| stats.latencies.iter().copied())
+    }
+}
+
+/// a model class representing statistics about the requests made over the course of a game
+/// not meant for the user to construct, but is returned in the ``stats`` property of the :class:`Akinator` class
+///
+/// the statistics are reset whenever a new game is started
+#[pyclass(module = "akinator")]
+#[derive(Debug, Clone)]
+pub struct GameStats(
+    pub StatsModel,
+);
+
+#[pymethods]
+impl GameStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "<GameStats total_requests={} total_latency={:.3} retries={} backs={}>",
+            self.total_requests(),
+            self.total_latency(),
+            self.retries(),
+            self.backs(),
+        )
+    }
+
+    /// Converts these statistics into a :class:`dict`
+    ///
+    /// Returns
+    /// -------
+    /// :class:`dict`
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.0.to_py(py)
+    }
+
+    /// Dict[:class:`str`, :class:`int`]: the number of requests made to each endpoint
+    #[getter]
+    fn requests(&self) -> HashMap<String, usize> {
+        self.0.endpoints
+            .iter()
+            .map(|(endpoint, stats)| (endpoint.clone(), stats.requests))
+            .collect()
+    }
+
+    /// Dict[:class:`str`, :class:`int`]: the number of requests made to each endpoint that failed
+    #[getter]
+    fn failures(&self) -> HashMap<String, usize> {
+        self.0.endpoints
+            .iter()
+            .map(|(endpoint, stats)| (endpoint.clone(), stats.failures))
+            .collect()
+    }
+
+    /// Dict[:class:`str`, List[:class:`float`]]: the latency in seconds of each request made to each endpoint, in order
+    #[getter]
+    fn latencies(&self) -> HashMap<String, Vec<f64>> {
+        self.0.endpoints
+            .iter()
+            .map(|(endpoint, stats)| (endpoint.clone(), stats.latencies.clone()))
+            .collect()
+    }
+
+    /// :class:`int`: the total number of requests made
+    #[getter]
+    fn total_requests(&self) -> usize {
+        self.0.endpoints
+            .values()
+            .map(|stats| stats.requests)
+            .sum()
+    }
+
+    /// :class:`float`: the total time in seconds spent waiting on requests
+    #[getter]
+    fn total_latency(&self) -> f64 {
+        self.0.latencies().sum()
+    }
+
+    /// :class:`int`: the number of requests that were retried
+    #[getter]
+    const fn retries(&self) -> usize {
+        self.0.retries
+    }
+
+    /// :class:`int`: the number of times the game went back a question
+    #[getter]
+    const fn backs(&self) -> usize {
+        self.0.backs
+    }
+
+    /// List[Tuple[:class:`int`, :class:`float`]]: the progression of the akinator after each question, as ``(step, progression)`` pairs
+    #[getter]
+    fn progression(&self) -> Vec<(usize, f32)> {
+        self.0.progression.clone()
+    }
+}
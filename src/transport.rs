@@ -0,0 +1,405 @@
+//! The layer the game sends its requests through,
+//! allowing the akinator servers to be swapped out for a local stand-in such as a :class:`MockServer`
+
+use crate::{
+    error::{
+        Error,
+        ErrorContext,
+        Result,
+    },
+    logging::{
+        log,
+        Level,
+    },
+};
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    fmt::Debug,
+    future::Future,
+    io,
+    path::PathBuf,
+    pin::Pin,
+    sync::{
+        Arc,
+        Mutex,
+        RwLock,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use reqwest::header::{
+    HeaderMap,
+    HeaderName,
+    HeaderValue,
+};
+
+use serde::{
+    Serialize,
+    Deserialize,
+};
+
+const USER_AGENT: &str =
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/81.0.4044.92 Safari/537.36";
+
+/// the future returned by [`Transport::get`], resolving to the response body
+pub type ResponseFuture<'a> = Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>>;
+
+/// a backend capable of answering the game's requests
+pub trait Transport: Debug + Send + Sync {
+    /// sends a ``GET`` request to ``url`` with the query ``params``
+    /// and returns the body of the response
+    fn get<'a>(&'a self, url: &'a str, params: &'a [(&'a str, String)]) -> ResponseFuture<'a>;
+}
+
+/// the options used to build the HTTP client of an [`HttpTransport`]
+#[derive(Debug, Clone, Default)]
+pub struct HttpConfig {
+    pub proxy: Option<String>,
+    pub connect_timeout: Option<Duration>,
+    pub request_timeout: Option<Duration>,
+    pub user_agent: Option<String>,
+    pub headers: HashMap<String, String>,
+}
+
+/// converts a timeout given in seconds from python into a [`Duration`]
+pub(crate) fn timeout_from_secs(secs: f64) -> Result<Duration> {
+    Duration::try_from_secs_f64(secs)
+        .map_err(|_| Error::ConfigError(format!("invalid timeout: {secs}")))
+}
+
+impl HttpConfig {
+    fn build_client(&self) -> Result<reqwest::Client> {
+        let mut headers = HeaderMap::new();
+
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| Error::ConfigError(format!("invalid header name: {name:?}")))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| Error::ConfigError(format!("invalid value for the {name} header: {value:?}")))?;
+
+            headers.insert(name, value);
+        }
+
+        let mut builder = reqwest::Client::builder()
+            .user_agent(self.user_agent.as_deref().unwrap_or(USER_AGENT))
+            .default_headers(headers)
+            .gzip(true);
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|_| Error::ConfigError(format!("invalid proxy url: {proxy:?}")))?;
+
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+/// the default transport, talking to the real akinator servers over HTTP
+///
+/// the underlying client can be rebuilt in place with a new [`HttpConfig`],
+/// so that any transports wrapping this one pick up the change as well
+///
+/// once closed, the client (and with it its connection pool) is dropped,
+/// and any further requests fail with [`Error::SessionClosed`]
+#[derive(Debug)]
+pub struct HttpTransport {
+    config: RwLock<HttpConfig>,
+    client: RwLock<Option<reqwest::Client>>,
+}
+
+impl HttpTransport {
+    pub fn new(config: HttpConfig) -> Result<Self> {
+        let client = config.build_client()?;
+
+        Ok(Self {
+            config: RwLock::new(config),
+            client: RwLock::new(Some(client)),
+        })
+    }
+
+    pub fn config(&self) -> Result<HttpConfig> {
+        Ok(self.config.read()?.clone())
+    }
+
+    /// rebuilds the HTTP client using ``config``
+    pub fn configure(&self, config: HttpConfig) -> Result<()> {
+        let client = config.build_client()?;
+        let mut current = self.client.write()?;
+
+        // a closed transport stays closed, only its options are updated
+        if current.is_some() {
+            *current = Some(client);
+        }
+        *self.config.write()? = config;
+
+        Ok(())
+    }
+
+    /// drops the HTTP client, releasing its pooled connections
+    pub fn close(&self) -> Result<()> {
+        self.client.write()?.take();
+
+        Ok(())
+    }
+}
+
+impl Transport for HttpTransport {
+    fn get<'a>(&'a self, url: &'a str, params: &'a [(&'a str, String)]) -> ResponseFuture<'a> {
+        Box::pin(async move {
+            // cloning the client is cheap, and avoids holding the lock across the request
+            let client = self.client
+                .read()?
+                .clone()
+                .ok_or(Error::SessionClosed)?;
+
+            let started = Instant::now();
+            let response = client
+                .get(url)
+                .query(params)
+                .send()
+                .await?;
+
+            let status = response.status().as_u16();
+            let latency = started.elapsed().as_secs_f64();
+
+            log!(
+                Level::Debug,
+                format!("GET {url} responded with {status} in {:.0}ms", latency * 1000.0),
+                url = url,
+                status = status,
+                latency = latency,
+            );
+
+            if let Err(err) = response.error_for_status_ref() {
+                let body = response.text().await.unwrap_or_default();
+
+                return Err(Error::from(err).with_context(ErrorContext::response(Some(status), &body)));
+            }
+
+            Ok(response.text().await?)
+        })
+    }
+}
+
+/// a single recorded request and the response it received
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    url: String,
+    params: Vec<(String, String)>,
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+/// what a recorded request led to, either the body of its response or the error it failed with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Outcome {
+    Response(String),
+    Error(RecordedError),
+}
+
+/// an error a recorded request failed with, such as a timeout or an error status of the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedError {
+    kind: String,
+    message: String,
+    #[serde(default)]
+    unprocessed: bool,
+    #[serde(default)]
+    status: Option<u16>,
+    #[serde(default)]
+    snippet: Option<String>,
+}
+
+impl RecordedError {
+    fn new(error: &Error) -> Self {
+        let context = error.context()
+            .cloned()
+            .unwrap_or_default();
+
+        Self {
+            kind: error.kind().to_string(),
+            message: format!("{:?}", error.inner()),
+            unprocessed: error.is_unprocessed(),
+            status: context.status,
+            snippet: context.snippet,
+        }
+    }
+
+    fn into_error(self) -> Error {
+        let context = ErrorContext {
+            status: self.status,
+            snippet: self.snippet,
+            ..ErrorContext::default()
+        };
+
+        Error::Replayed {
+            kind: self.kind,
+            message: self.message,
+            unprocessed: self.unprocessed,
+        }
+        .with_context(context)
+    }
+}
+
+/// the parameters that are expected to differ between recording and replaying a game,
+/// such as the JSONP callback which is named after the time the game was started
+const VOLATILE_PARAMS: [&str; 1] = ["callback"];
+
+/// the parameters of a request that have to match when replaying it
+fn stable_params<'a>(params: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<(&'a str, &'a str)> {
+    params.into_iter()
+        .filter(|(key, _)| !VOLATILE_PARAMS.contains(key))
+        .collect()
+}
+
+/// the contents of a cassette file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+/// wraps another transport, writing every request that goes through it to a cassette file,
+/// along with either its response or the error it failed with
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+    /// the number of interactions written to the file so far,
+    /// so that a write that was overtaken by a later one does not overwrite it with older contents
+    written: Arc<Mutex<usize>>,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Arc<dyn Transport>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            cassette: Mutex::new(Cassette::default()),
+            written: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    async fn record(&self, url: &str, params: &[(&str, String)], outcome: Outcome) -> Result<()> {
+        let (recorded, contents) = {
+            let mut cassette = self.cassette.lock()?;
+
+            cassette.interactions.push(Interaction {
+                url: url.to_string(),
+                params: params.iter()
+                    .map(|(key, value)| ((*key).to_string(), value.clone()))
+                    .collect(),
+                outcome,
+            });
+
+            (cassette.interactions.len(), serde_json::to_string_pretty(&*cassette)?)
+        };
+
+        let path = self.path.clone();
+        let written = Arc::clone(&self.written);
+
+        // rewritten after every request, so that the cassette is still usable if the game is abandoned midway,
+        // on a blocking thread as to not stall the other games running on the runtime
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut written = written.lock()?;
+
+            if recorded > *written {
+                std::fs::write(path, contents)?;
+                *written = recorded;
+            }
+
+            Ok(())
+        })
+        .await
+        .map_err(io::Error::from)?
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn get<'a>(&'a self, url: &'a str, params: &'a [(&'a str, String)]) -> ResponseFuture<'a> {
+        Box::pin(async move {
+            match self.inner.get(url, params).await {
+                Ok(response) => {
+                    self.record(url, params, Outcome::Response(response.clone())).await?;
+
+                    Ok(response)
+                },
+                Err(err) => {
+                    self.record(url, params, Outcome::Error(RecordedError::new(&err))).await?;
+
+                    Err(err)
+                },
+            }
+        })
+    }
+}
+
+/// serves the responses (and errors) of a cassette file written by a [`RecordingTransport`] back, in order
+#[derive(Debug)]
+pub struct ReplayTransport {
+    interactions: Mutex<VecDeque<Interaction>>,
+}
+
+impl ReplayTransport {
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let contents = std::fs::read_to_string(path.into())?;
+        let cassette: Cassette = serde_json::from_str(&contents)?;
+
+        Ok(Self {
+            interactions: Mutex::new(cassette.interactions.into()),
+        })
+    }
+
+    fn replay(&self, url: &str, params: &[(&str, String)]) -> Result<String> {
+        let interaction = self.interactions
+            .lock()?
+            .pop_front()
+            .ok_or_else(|| Error::CassetteError(format!("no recorded response left for a request to {url}")))?;
+
+        if interaction.url != url {
+            return Err(Error::CassetteError(
+                format!("expected a request to {}, but got a request to {url}", interaction.url)
+            ));
+        }
+
+        let expected = stable_params(interaction.params.iter().map(|(key, value)| (key.as_str(), value.as_str())));
+        let actual = stable_params(params.iter().map(|(key, value)| (*key, value.as_str())));
+
+        if expected != actual {
+            return Err(Error::CassetteError(
+                format!("expected a request to {url} with {expected:?}, but got one with {actual:?}")
+            ));
+        }
+
+        match interaction.outcome {
+            Outcome::Response(response) => Ok(response),
+            Outcome::Error(error) => Err(error.into_error()),
+        }
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn get<'a>(&'a self, url: &'a str, params: &'a [(&'a str, String)]) -> ResponseFuture<'a> {
+        Box::pin(async move {
+            self.replay(url, params)
+        })
+    }
+}
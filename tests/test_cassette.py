@@ -0,0 +1,76 @@
+import json
+import os
+import tempfile
+
+from akinator import (
+    CassetteError,
+    TimeoutError,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def play(aki: Akinator) -> list:
+    # plays the same scripted game, returning everything observed along the way
+    observed = [aki.start_game()]
+    observed.append(aki.answer(Answer.Yes))
+    observed.append(aki.back())
+    observed.append(aki.answer(Answer.No))
+    observed.append(aki.win().name)
+    return observed
+
+def test() -> None:
+    with tempfile.TemporaryDirectory() as directory:
+        path = os.path.join(directory, 'session.json')
+
+        # record a session played against the mock server
+        recorded = play(Akinator(transport=MockServer(), record=path))
+        assert os.path.exists(path)
+
+        # and replay it without any server at all
+        replayed = play(Akinator(replay=path))
+        assert replayed == recorded
+
+        # the cassette runs out once every response has been served
+        aki = Akinator(replay=path)
+        play(aki)
+        try:
+            # the game is left guessing, where the guesses can be fetched again
+            aki.win()
+        except CassetteError as error:
+            assert 'no recorded response left' in str(error)
+        else:
+            raise AssertionError('expected the cassette to be exhausted')
+
+        # requests are matched on their parameters as well, such as the answer given
+        aki = Akinator(replay=path)
+        aki.start_game()
+        try:
+            aki.answer(Answer.No)
+        except CassetteError as error:
+            assert '("answer", "1")' in str(error)
+        else:
+            raise AssertionError('expected the answer to differ from the recorded one')
+
+        # and the errors requests failed with are replayed as well
+        with open(path) as file:
+            cassette = json.load(file)
+
+        failed = cassette['interactions'][-1]
+        del failed['response']
+        failed['error'] = {'kind': 'TimeoutError', 'message': 'operation timed out', 'status': 504}
+
+        with open(path, 'w') as file:
+            json.dump(cassette, file)
+
+        aki = Akinator(replay=path)
+        try:
+            play(aki)
+        except TimeoutError as error:
+            assert 'operation timed out' in str(error)
+            assert error.status == 504
+        else:
+            raise AssertionError('expected the recorded error to be raised')
+
+if __name__ == '__main__':
+    test()
@@ -0,0 +1,78 @@
+import builtins
+
+import akinator
+from akinator import (
+    AkinatorError,
+    TechnicalError,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def test_hierarchy() -> None:
+    for name in (
+        'CantGoBackAnyFurther', 'InvalidAnswer', 'InvalidLanguage', 'ConnectionError',
+        'NoMoreQuestions', 'TimeoutError', 'TechnicalError', 'ServersDown', 'SessionClosed', 'SessionDiverged',
+        'GameFinished', 'RequestError', 'JsonParseError', 'NoDataFound', 'UpdateInfoError', 'TimeError',
+        'InvalidGameState', 'MissingData', 'CassetteError', 'ConfigError', 'UnansweredQuestion', 'PoisonError',
+    ):
+        assert issubclass(getattr(akinator, name), AkinatorError), name
+
+    # the context attributes default to `None`
+    err = TechnicalError()
+    assert (err.step, err.question, err.status, err.snippet) == (None, None, None, None)
+
+def test_builtin_bases() -> None:
+    assert issubclass(akinator.TimeoutError, builtins.TimeoutError)
+    assert issubclass(akinator.ConnectionError, builtins.ConnectionError)
+    assert issubclass(akinator.InvalidAnswer, ValueError)
+    assert issubclass(akinator.InvalidLanguage, ValueError)
+    assert issubclass(akinator.MissingData, RuntimeError)
+    assert issubclass(akinator.CassetteError, RuntimeError)
+    assert issubclass(akinator.ConfigError, ValueError)
+    assert issubclass(akinator.UnansweredQuestion, RuntimeError)
+    assert issubclass(akinator.PoisonError, RuntimeError)
+
+    # so generic handlers of the builtins catch them too
+    server = MockServer()
+    aki = Akinator(transport=server)
+    aki.start_game()
+
+    server.inject_error('TimeoutError')
+    try:
+        aki.answer(Answer.Yes)
+    except builtins.TimeoutError as err:
+        assert isinstance(err, akinator.TimeoutError)
+        assert err.step == 0
+    else:
+        raise AssertionError('expected TimeoutError')
+
+    try:
+        Answer.from_str('definitely')
+    except ValueError as err:
+        assert isinstance(err, akinator.InvalidAnswer)
+    else:
+        raise AssertionError('expected InvalidAnswer')
+
+def test_context() -> None:
+    server = MockServer()
+    aki = Akinator(transport=server)
+    aki.start_game()
+    aki.answer(Answer.Yes)
+
+    server.inject_error('TechnicalError')
+    try:
+        aki.answer(Answer.No)
+    except AkinatorError as err:
+        assert isinstance(err, TechnicalError)
+        assert err.step == 1
+        assert err.question == aki.question
+        assert err.status is None
+        assert 'KO - TECHNICAL ERROR' in err.snippet
+    else:
+        raise AssertionError('expected TechnicalError')
+
+if __name__ == '__main__':
+    test_hierarchy()
+    test_builtin_bases()
+    test_context()
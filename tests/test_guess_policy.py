@@ -0,0 +1,118 @@
+import asyncio
+
+from akinator import (
+    AsyncAkinator,
+    AnswerResult,
+    GuessPolicy,
+    MockServer,
+    GameState,
+    Akinator,
+    Answer,
+)
+
+QUESTIONS = ['first', 'second', 'third', 'fourth', 'fifth']
+
+def test_progression() -> None:
+    server = MockServer(
+        questions=QUESTIONS,
+        progression=[0.0, 40.0, 85.0, 90.0, 95.0],
+        guesses=[{'name': 'Mario', 'confidence': 0.9}],
+    )
+    aki = Akinator(transport=server, guess_policy=GuessPolicy(progression=80.0))
+    assert aki.guess_policy.progression == 80.0
+    aki.start_game()
+
+    result = aki.answer(Answer.Yes)
+    assert isinstance(result, AnswerResult)
+    assert result.kind == 'question'
+    assert not result.is_guess
+    assert result.question == 'second' == str(result)
+    assert result.guess is None
+
+    result = aki.answer(Answer.Yes)
+    assert result.kind == 'guess'
+    assert result.guess.name == 'Mario'
+    assert aki.state == GameState.Guessing
+
+    # without a policy, answers return the next question as is
+    aki.guess_policy = None
+    aki.start_game()
+    assert aki.answer(Answer.Yes) == 'second'
+
+    # answer_with_policy falls back to the default policy when the game has none
+    assert aki.answer_with_policy(Answer.Yes).is_guess
+
+    # and a policy can be passed for a single answer
+    aki.start_game()
+    assert aki.answer(Answer.Yes, GuessPolicy(progression=30.0)).is_guess
+
+def test_confidence() -> None:
+    server = MockServer(
+        questions=QUESTIONS,
+        progression=[0.0, 85.0, 90.0, 95.0, 99.0],
+        guesses=[{'name': 'Mario', 'confidence': 0.5}],
+    )
+    aki = Akinator(transport=server, guess_policy=GuessPolicy(confidence=0.8, max_steps=3))
+    aki.start_game()
+
+    # the guess is not confident enough, so the game goes on
+    result = aki.answer(Answer.Yes)
+    assert result.question == 'second'
+    assert server.requests[-1] == 'list'
+    assert aki.state == GameState.InProgress
+    assert aki.first_guess is None
+
+    assert aki.answer(Answer.Yes).question == 'third'
+
+    # until the maximum number of steps is reached
+    result = aki.answer(Answer.Yes)
+    assert result.is_guess
+    assert result.guess.name == 'Mario'
+
+def test_fallback_step() -> None:
+    server = MockServer(questions=QUESTIONS, progression=[0.0])
+    aki = Akinator(transport=server, guess_policy=GuessPolicy(fallback_step=2))
+    aki.start_game()
+
+    assert not aki.answer(Answer.Yes).is_guess
+    assert aki.answer(Answer.No).is_guess
+
+def test_exhausted() -> None:
+    aki = Akinator(transport=MockServer(questions=['only'], progression=[0.0]), guess_policy=GuessPolicy())
+    aki.start_game()
+
+    # the akinator has no more questions to ask, so it has to guess
+    assert aki.answer(Answer.Yes).is_guess
+
+def test_validation() -> None:
+    for kwargs in ({'progression': 120.0}, {'confidence': -0.5}):
+        try:
+            GuessPolicy(**kwargs)
+        except ValueError:
+            pass
+        else:
+            raise AssertionError('expected ValueError')
+
+async def test_async_policy() -> None:
+    server = MockServer(questions=QUESTIONS, progression=[0.0, 90.0])
+    aki = AsyncAkinator(transport=server, guess_policy=GuessPolicy())
+    await aki.start_game()
+
+    result = await aki.answer(Answer.Yes)
+    assert result.is_guess
+    assert aki.guess_policy is not None
+
+    aki.guess_policy = None
+    await aki.start_game()
+    assert await aki.answer(Answer.Yes) == 'second'
+
+    await aki.start_game()
+    assert (await aki.answer_with_policy(Answer.Yes)).is_guess
+
+if __name__ == '__main__':
+    test_progression()
+    test_confidence()
+    test_fallback_step()
+    test_exhausted()
+    test_validation()
+    asyncio.run(test_async_policy())
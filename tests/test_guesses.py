@@ -0,0 +1,114 @@
+import asyncio
+
+from akinator import (
+    AsyncAkinator,
+    GameFinished,
+    GameResult,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+GUESSES = [
+    {'name': 'Mario', 'confidence': 0.9},
+    {'name': 'Luigi', 'confidence': 0.8},
+]
+
+def test_reject_guess() -> None:
+    aki = Akinator(transport=MockServer(guesses=GUESSES))
+    aki.start_game()
+    aki.answer(Answer.Yes)
+
+    try:
+        aki.reject_guess()
+    except RuntimeError:
+        pass
+    else:
+        raise AssertionError('expected RuntimeError')
+
+    assert aki.win().name == 'Mario'
+    assert aki.reject_guess() is not None
+    assert aki.step == 2
+    assert aki.first_guess is None
+    assert [guess.name for guess in aki.rejected_guesses] == ['Mario']
+
+    # the rejected guess is left out of the next proposals
+    assert aki.win().name == 'Luigi'
+    assert aki.continue_game() is not None
+    assert aki.step == 3
+    assert [guess.name for guess in aki.rejected_guesses] == ['Mario', 'Luigi']
+
+    restored = Akinator.from_state(aki.to_dict())
+    assert len(restored.rejected_guesses) == 2
+
+    aki.start_game()
+    assert aki.rejected_guesses == []
+
+def test_confirm_guess() -> None:
+    server = MockServer(guesses=GUESSES)
+    aki = Akinator(transport=server)
+    aki.start_game()
+    aki.answer(Answer.Yes)
+
+    try:
+        aki.confirm_guess()
+    except RuntimeError:
+        pass
+    else:
+        raise AssertionError('expected RuntimeError')
+
+    aki.win()
+    assert not aki.finished
+
+    result = aki.confirm_guess()
+    assert result.guess.name == 'Mario'
+    assert result.step == 1
+    assert result.times_played == 1
+    assert aki.finished
+    assert aki.result.guess.name == 'Mario'
+    assert server.requests[-1] == 'choice'
+
+    for call in (lambda: aki.answer(Answer.Yes), aki.back, aki.confirm_guess):
+        try:
+            call()
+        except GameFinished:
+            pass
+        else:
+            raise AssertionError('expected GameFinished')
+
+    restored = Akinator.from_state(aki.to_dict())
+    assert restored.finished
+    assert GameResult.from_dict(result.to_dict()).times_played == 1
+
+    # confirming one of the other guesses, in a new game
+    aki.start_game()
+    assert not aki.finished
+    aki.win()
+    assert aki.confirm_guess(aki.guesses[1]).guess.name == 'Luigi'
+    assert aki.result.times_played == 1
+
+async def test_async_reject_guess() -> None:
+    aki = AsyncAkinator(transport=MockServer(guesses=GUESSES))
+    await aki.start_game()
+
+    assert (await aki.win()).name == 'Mario'
+    assert await aki.reject_guess() is not None
+    assert aki.step == 1
+    assert (await aki.win()).name == 'Luigi'
+    assert len(aki.rejected_guesses) == 1
+
+    result = await aki.confirm_guess()
+    assert result.guess.name == 'Luigi'
+    assert aki.finished
+
+    try:
+        await aki.answer(Answer.No)
+    except GameFinished:
+        pass
+    else:
+        raise AssertionError('expected GameFinished')
+
+if __name__ == '__main__':
+    test_reject_guess()
+    test_confirm_guess()
+    asyncio.run(test_async_reject_guess())
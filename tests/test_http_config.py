@@ -0,0 +1,37 @@
+from akinator import (
+    AsyncAkinator,
+    Akinator,
+)
+
+def test() -> None:
+    for cls in (Akinator, AsyncAkinator):
+        aki = cls(
+            proxy='http://proxy.example.com:8080',
+            connect_timeout=2.5,
+            request_timeout=10,
+            user_agent='my-bot/1.0',
+            headers={'X-Trace': 'abc'},
+        )
+
+        assert aki.proxy == 'http://proxy.example.com:8080'
+        assert aki.connect_timeout == 2.5
+        assert aki.request_timeout == 10.0
+        assert aki.user_agent == 'my-bot/1.0'
+        assert aki.headers == {'X-Trace': 'abc'}
+
+        # the client is rebuilt when the options change
+        aki.proxy = None
+        aki.request_timeout = 5
+        assert aki.proxy is None
+        assert aki.request_timeout == 5.0
+
+        for options in ({'headers': {'bad header': 'x'}}, {'connect_timeout': -1}):
+            try:
+                cls(**options)
+            except ValueError:
+                pass
+            else:
+                raise AssertionError(f'expected {options} to be rejected')
+
+if __name__ == '__main__':
+    test()
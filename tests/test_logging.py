@@ -0,0 +1,59 @@
+import logging
+
+from akinator import (
+    RetryPolicy,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+class Collector(logging.Handler):
+    def __init__(self) -> None:
+        super().__init__()
+        self.records = []
+
+    def emit(self, record: logging.LogRecord) -> None:
+        self.records.append(record)
+
+def test() -> None:
+    collector = Collector()
+    logger = logging.getLogger('akinator')
+    logger.addHandler(collector)
+    logger.setLevel(logging.DEBUG)
+
+    server = MockServer()
+    aki = Akinator(transport=server, retry=RetryPolicy(base_delay=0.01))
+    aki.start_game()
+
+    server.inject_error('TechnicalError')
+    aki.answer(Answer.Yes)
+
+    answers = [record for record in collector.records if getattr(record, 'endpoint', None) == 'answer_api']
+    assert [record.levelno for record in answers] == [logging.WARNING, logging.DEBUG]
+
+    failed, succeeded = answers
+    assert failed.error_kind == 'TechnicalError'
+    assert failed.step == 0
+    assert failed.url.endswith('/answer_api')
+    assert succeeded.latency >= 0.0
+
+    retries = [record for record in collector.records if record.levelno == logging.INFO]
+    assert len(retries) == 1
+    assert retries[0].attempt == 2
+
+    # nothing is emitted when the logger is disabled for the level
+    collector.records.clear()
+    logger.setLevel(logging.WARNING)
+    aki.answer(Answer.No)
+    assert collector.records == []
+
+    # and enabling a level mid-game applies to the very next request
+    logger.setLevel(logging.DEBUG)
+    aki.answer(Answer.Yes)
+    assert [record.endpoint for record in collector.records] == ['answer_api']
+    assert collector.records[0].step == 2
+
+    logger.removeHandler(collector)
+
+if __name__ == '__main__':
+    test()
@@ -0,0 +1,326 @@
+import asyncio
+import pickle
+
+from akinator import (
+    CantGoBackAnyFurther,
+    UnansweredQuestion,
+    NoMoreQuestions,
+    AsyncAkinator,
+    SessionClosed,
+    GameState,
+    TimeoutError,
+    ServersDown,
+    MockServer,
+    Akinator,
+    Answer,
+    Theme,
+)
+
+def test_game_flow() -> None:
+    server = MockServer(
+        questions=['first', 'second', 'third'],
+        tree={'first': {'No': 'branch'}},
+        progression=[0.0, 50.0, 90.0],
+        guesses=[{'name': 'Mario', 'confidence': 0.97}],
+    )
+    aki = Akinator(transport=server)
+
+    assert aki.start_game() == 'first'
+    assert aki.answer(Answer.Yes) == 'second'
+    assert aki.progression == 50.0
+
+    # going back and taking the other branch of the tree
+    assert aki.back() == 'first'
+    assert aki.answer(Answer.No) == 'branch'
+    assert aki.answer(Answer.Yes) == 'third'
+    assert aki.step == 2
+
+    guess = aki.win()
+    assert guess is not None
+    assert guess.name == 'Mario'
+    assert abs(guess.confidence - 0.97) < 1e-6
+
+    # going back from the guess, until the akinator runs out of questions and the game is lost
+    assert aki.back() == 'branch'
+    assert aki.answer(Answer.Yes) == 'third'
+
+    try:
+        aki.answer(Answer.Yes)
+    except NoMoreQuestions:
+        pass
+    else:
+        raise AssertionError('expected NoMoreQuestions')
+
+    assert aki.state == GameState.Lost
+
+    assert server.sessions == 1
+    assert server.requests[:3] == ['new_session', 'answer_api', 'cancel_answer']
+
+def test_injected_errors() -> None:
+    server = MockServer(errors={1: 'ServersDown'})
+    aki = Akinator(transport=server)
+    aki.start_game()
+
+    try:
+        aki.back()
+    except CantGoBackAnyFurther:
+        pass
+
+    aki.answer(Answer.Yes)
+
+    try:
+        aki.answer(Answer.Yes)
+    except ServersDown:
+        pass
+    else:
+        raise AssertionError('expected ServersDown')
+
+    # the error is only injected once
+    aki.answer(Answer.Yes)
+    assert aki.step == 2
+
+    # and the errors of the first step are not raised when starting the game
+    aki = Akinator(transport=MockServer(errors={0: 'ServersDown'}))
+    aki.start_game()
+
+    try:
+        aki.answer(Answer.Yes)
+    except ServersDown:
+        pass
+    else:
+        raise AssertionError('expected ServersDown')
+
+def test_resume() -> None:
+    server = MockServer()
+    aki = Akinator(transport=server)
+    aki.start_game()
+    aki.answer(Answer.No)
+
+    resumed = Akinator.from_state(aki.to_dict(), transport=server)
+    resumed.answer(Answer.Yes)
+
+    assert resumed.step == 2
+
+def test_history() -> None:
+    server = MockServer(progression=[0.0, 20.0, 40.0, 60.0])
+    aki = Akinator(transport=server)
+
+    first = aki.start_game()
+    second = aki.answer(Answer.Yes)
+    aki.answer(Answer.No)
+
+    history = aki.history
+    assert [entry.step for entry in history] == [0, 1]
+    assert [entry.question for entry in history] == [first, second]
+    assert repr(history[1].answer) == repr(Answer.No)
+    assert history[1].progression_before == 20.0
+    assert history[1].progression_after == 40.0
+    assert history[0].timestamp <= history[1].timestamp
+
+    # going back undoes the last answer
+    aki.back()
+    assert [entry.step for entry in aki.history] == [0]
+
+    aki.answer(Answer.Probably)
+    assert repr(aki.history[-1].answer) == repr(Answer.Probably)
+
+    resumed = Akinator.from_state(aki.to_json(), transport=server)
+    assert len(resumed.history) == 2
+
+    entry = pickle.loads(pickle.dumps(aki.history[0]))
+    assert entry.to_dict() == aki.history[0].to_dict()
+
+    aki.start_game()
+    assert aki.history == []
+
+def test_questions() -> None:
+    server = MockServer(progression=[0.0, 30.0, 60.0, 90.0])
+    aki = Akinator(transport=server)
+
+    steps = []
+    for question in aki.questions(threshold=80):
+        steps.append(question.step)
+        assert str(question) == question.text == aki.question
+        question.answer(Answer.Yes)
+
+    assert steps == [0, 1, 2]
+    assert aki.progression == 90.0
+
+    # the iteration also ends when the akinator runs out of questions
+    aki = Akinator(transport=MockServer(questions=['only']))
+    for question in aki.questions(threshold=100):
+        question.answer(Answer.No)
+
+    assert aki.step == 0
+
+    # a question has to be answered before moving on
+    aki = Akinator(transport=MockServer())
+    questions = aki.questions()
+    next(questions)
+    try:
+        next(questions)
+    except UnansweredQuestion:
+        pass
+    else:
+        raise AssertionError('expected UnansweredQuestion')
+
+def test_context_manager() -> None:
+    with Akinator(transport=MockServer()) as aki:
+        assert aki.step == 0
+        assert aki.question is not None
+        aki.answer(Answer.Yes)
+
+    assert aki.closed
+    try:
+        aki.answer(Answer.Yes)
+    except SessionClosed:
+        pass
+    else:
+        raise AssertionError('expected SessionClosed')
+
+    # the game is closed even when the block raises
+    try:
+        with Akinator(transport=MockServer()) as aki:
+            raise KeyError
+    except KeyError:
+        pass
+
+    assert aki.closed
+
+    # closing also releases the HTTP client
+    aki = Akinator()
+    aki.close()
+    try:
+        aki.start_game()
+    except SessionClosed:
+        pass
+    else:
+        raise AssertionError('expected SessionClosed')
+
+def test_server_failover() -> None:
+    primary = 'https://srv1.mock.akinator.local/ws'
+    fallback = 'https://srv2.mock.akinator.local/ws'
+
+    aki = Akinator(transport=MockServer(servers=[primary, fallback], down_servers=[primary]))
+    assert aki.server is None
+
+    aki.start_game()
+    assert aki.server == fallback
+
+    aki.answer(Answer.Yes)
+    assert aki.win() is not None
+
+    aki = Akinator(transport=MockServer(servers=[primary, fallback], down_servers=[primary, fallback]))
+    try:
+        aki.start_game()
+    except ServersDown:
+        pass
+    else:
+        raise AssertionError('expected ServersDown')
+
+def test_expired_sessions() -> None:
+    server = MockServer(guesses=[{'name': 'Mario'}])
+    aki = Akinator(transport=server)
+    aki.start_game()
+    copy = Akinator.from_state(aki.to_dict(), transport=server)
+
+    server.expire_sessions()
+
+    # every request made to an expired session times out, without bringing it back
+    for game in (aki, copy):
+        try:
+            game.win()
+        except TimeoutError:
+            pass
+        else:
+            raise AssertionError('expected TimeoutError')
+
+async def test_async_game_flow() -> None:
+    server = MockServer()
+    aki = AsyncAkinator(transport=server)
+
+    server.inject_error('TimeoutError')
+    try:
+        await aki.start_game()
+    except TimeoutError:
+        pass
+    else:
+        raise AssertionError('expected TimeoutError')
+
+    assert await aki.start_game() is not None
+    await aki.answer(Answer.Probably)
+    assert aki.step == 1
+    assert len(aki.history) == 1
+
+    guess = await aki.win()
+    assert guess is not None
+
+    aki = AsyncAkinator(transport=MockServer())
+    steps = []
+    async for question in aki.questions(threshold=50):
+        steps.append(question.step)
+        await aki.answer(Answer.Yes)
+
+    assert steps == [0, 1, 2]
+
+    async with AsyncAkinator(transport=MockServer()) as aki:
+        assert aki.question is not None
+
+    assert aki.closed
+    try:
+        await aki.back()
+    except SessionClosed:
+        pass
+    else:
+        raise AssertionError('expected SessionClosed')
+
+async def test_async_snapshot() -> None:
+    server = MockServer(latency=1.0)
+    aki = AsyncAkinator(transport=server)
+    await aki.start_game()
+
+    task = asyncio.ensure_future(aki.answer(Answer.Yes))
+    while server.in_flight_requests == 0:
+        await asyncio.sleep(0.01)
+
+    # reading and setting properties does not wait on the in-flight request,
+    # which would have moved the game on to the next step
+    assert aki.step == 0
+    aki.theme = Theme.Animals
+    assert str(aki.theme) == 'Animals'
+    assert server.in_flight_requests == 1
+    assert not task.done()
+
+    await task
+    assert aki.step == 1
+    assert str(aki.theme) == 'Animals'
+    assert aki.to_dict()['theme'] == 'Animals'
+
+async def test_cancelled_request() -> None:
+    server = MockServer(latency=0.2)
+    aki = AsyncAkinator(transport=server)
+    await aki.start_game()
+
+    task = asyncio.ensure_future(aki.answer(Answer.Yes))
+    while server.in_flight_requests == 0:
+        await asyncio.sleep(0.01)
+
+    task.cancel()
+
+    # the next request waits on the game until the cancelled one has been dropped
+    await aki.answer(Answer.Yes)
+    assert aki.step == 1
+    assert server.in_flight_requests == 0
+
+if __name__ == '__main__':
+    test_game_flow()
+    test_injected_errors()
+    test_resume()
+    test_history()
+    test_questions()
+    test_context_manager()
+    test_server_failover()
+    test_expired_sessions()
+    asyncio.run(test_async_game_flow())
+    asyncio.run(test_async_snapshot())
+    asyncio.run(test_cancelled_request())
@@ -0,0 +1,59 @@
+import pickle
+
+from akinator import (
+    AsyncAkinator,
+    MockServer,
+    Akinator,
+    Language,
+    Answer,
+    Theme,
+)
+
+def test() -> None:
+    # enum variants are pickled by name
+    for variant in (Theme.Animals, Answer.ProbablyNot, Language.French):
+        assert repr(pickle.loads(pickle.dumps(variant))) == repr(variant)
+
+    # the full game state is carried over
+    aki = Akinator(
+        child_mode=True,
+        theme=Theme.Objects,
+        language=Language.German,
+    )
+    restored = pickle.loads(pickle.dumps(aki))
+
+    assert restored.to_dict() == aki.to_dict()
+    assert str(restored.theme) == 'Objects'
+    assert str(restored.language) == 'German'
+    assert restored.child_mode
+
+    async_aki = AsyncAkinator(theme=Theme.Animals)
+    restored = pickle.loads(pickle.dumps(async_aki))
+
+    assert restored.to_json() == async_aki.to_json()
+
+def test_mid_game() -> None:
+    server = MockServer(questions=['first', 'second', 'third', 'fourth'])
+    aki = Akinator(transport=server, auto_renew=True)
+    aki.start_game()
+    aki.answer(Answer.Yes)
+    aki.answer(Answer.No)
+
+    restored = pickle.loads(pickle.dumps(aki))
+    assert restored.step == 2
+    assert restored.question == 'third'
+    assert [entry.answer for entry in restored.history] == [Answer.Yes, Answer.No]
+    assert restored.auto_renew
+
+    # the transport is not pickled, so the restored game has to be pointed at the server again
+    assert restored.transport is None
+    restored.transport = server
+
+    # and carries on with the same session
+    assert restored.answer(Answer.Idk) == 'fourth'
+    assert restored.step == 3
+    assert server.sessions == 1
+
+if __name__ == '__main__':
+    test()
+    test_mid_game()
@@ -0,0 +1,101 @@
+import asyncio
+import time
+
+from akinator import (
+    SessionClosed,
+    AkinatorPool,
+    MockServer,
+    Answer,
+    Theme,
+)
+
+async def test_lookup() -> None:
+    pool = AkinatorPool(transport=MockServer())
+
+    aki = pool.get_or_create(1)
+    assert pool.get(1) is aki
+    assert pool.get_or_create(1) is aki
+    assert pool.get(2) is None
+
+    await aki.start_game()
+    await aki.answer(Answer.Yes)
+    assert pool.get(1).step == 1
+
+    animals = pool.create('animals', theme=Theme.Animals)
+    assert animals.theme == Theme.Animals
+    assert sorted(pool.keys(), key=str) == [1, 'animals']
+    assert pool.active_sessions == len(pool) == 2
+    assert 'animals' in pool
+
+    # creating a game under an existing key replaces (and closes) the previous one
+    replaced = pool.create(1)
+    assert replaced is not aki
+    assert aki.closed
+    assert pool.active_sessions == 2
+
+    assert pool.remove('animals')
+    assert not pool.remove('animals')
+    assert animals.closed
+    assert pool.active_sessions == 1
+
+async def test_eviction() -> None:
+    pool = AkinatorPool(transport=MockServer(), max_sessions=2)
+
+    first = pool.create('first')
+    second = pool.create('second')
+    await first.start_game()
+
+    # the least recently used game makes room for a new one
+    pool.get('first')
+    pool.create('third')
+    assert second.closed
+    assert not first.closed
+    assert sorted(pool.keys()) == ['first', 'third']
+
+async def test_ttl() -> None:
+    pool = AkinatorPool(transport=MockServer(), ttl=0.0)
+    aki = pool.create('idle')
+    await aki.start_game()
+
+    # any time spent idle is longer than a ttl of 0
+    time.sleep(0.01)
+    assert pool.active_sessions == 0
+    assert 'idle' not in pool
+    assert pool.evict_expired() == 1
+    assert aki.closed
+
+    try:
+        await aki.answer(Answer.Yes)
+    except SessionClosed:
+        pass
+    else:
+        raise AssertionError('expected SessionClosed')
+
+async def test_activity() -> None:
+    pool = AkinatorPool(transport=MockServer(), max_sessions=2)
+    playing = pool.create('playing')
+    await playing.start_game()
+    waiting = pool.create('waiting')
+
+    # requests made by a game count as activity, even though it is not looked up again
+    time.sleep(0.01)
+    await playing.answer(Answer.Yes)
+    pool.create('new')
+    assert waiting.closed
+    assert not playing.closed
+
+async def test_close() -> None:
+    pool = AkinatorPool(transport=MockServer())
+    games = [pool.create(key) for key in range(3)]
+    await asyncio.gather(*(aki.start_game() for aki in games))
+
+    pool.close()
+    assert pool.active_sessions == 0
+    assert all(aki.closed for aki in games)
+
+if __name__ == '__main__':
+    asyncio.run(test_lookup())
+    asyncio.run(test_eviction())
+    asyncio.run(test_ttl())
+    asyncio.run(test_activity())
+    asyncio.run(test_close())
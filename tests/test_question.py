@@ -0,0 +1,75 @@
+import asyncio
+import pickle
+
+from akinator import (
+    AsyncAkinator,
+    GuessPolicy,
+    MockServer,
+    Question,
+    Akinator,
+    Answer,
+)
+
+def test_question_model() -> None:
+    aki = Akinator(transport=MockServer(questions=['first', 'second', 'third'], progression=[0.0, 50.0]))
+    assert aki.current_question is None
+
+    question = aki.start_game()
+    assert isinstance(question, Question)
+    assert question.text == 'first'
+    assert question.step == 0
+    assert question.progression == 0.0
+    assert question.id == '1'
+    assert question.answers == ['Yes', 'No', "Don't know", 'Probably', 'Probably not']
+
+    # still usable as the plain string it used to be
+    assert str(question) == 'first'
+    assert question == 'first'
+    assert 'first' == question
+    assert question != 'second'
+    assert f'{question}?' == 'first?'
+    assert question in {'first'}
+
+    question = aki.answer(Answer.Yes)
+    assert (question.text, question.step, question.progression, question.id) == ('second', 1, 50.0, '2')
+    assert aki.current_question == question
+    assert aki.question == 'second'
+
+    assert aki.back() == aki.current_question == 'first'
+    assert aki.current_question.step == 0
+
+    # questions can be pickled, such as along with the game they were asked in
+    restored = pickle.loads(pickle.dumps(aki.current_question))
+    assert isinstance(restored, Question)
+    assert restored == aki.current_question
+    assert (restored.id, restored.progression, restored.answers) == ('1', 0.0, question.answers)
+    assert Question.from_dict(restored.to_dict()) == restored
+
+    # the question is kept when saving and restoring a game
+    restored = Akinator.from_state(aki.to_dict())
+    assert restored.current_question.id == '1'
+    assert len(restored.current_question.answers) == 5
+
+def test_answer_result() -> None:
+    aki = Akinator(transport=MockServer(), guess_policy=GuessPolicy(progression=100.0))
+    aki.start_game()
+
+    result = aki.answer(Answer.Yes)
+    assert isinstance(result.question, Question)
+    assert result.question.step == 1
+
+async def test_async_question() -> None:
+    aki = AsyncAkinator(transport=MockServer())
+
+    question = await aki.start_game()
+    assert question.step == 0
+    assert aki.current_question == question
+
+    question = await aki.answer(Answer.No)
+    assert question.step == 1
+    assert question == aki.question
+
+if __name__ == '__main__':
+    test_question_model()
+    test_answer_result()
+    asyncio.run(test_async_question())
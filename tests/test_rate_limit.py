@@ -0,0 +1,66 @@
+import asyncio
+import time
+
+import akinator
+from akinator import (
+    AsyncAkinator,
+    AkinatorPool,
+    RateLimiter,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def test_rate_limiter() -> None:
+    limiter = RateLimiter(20.0)
+    aki = Akinator(transport=MockServer(), rate_limiter=limiter)
+    assert aki.rate_limiter.rate == 20.0
+
+    started = time.perf_counter()
+    aki.start_game()  # the home page, game page and new session requests
+    for _ in range(3):
+        aki.answer(Answer.Yes)
+
+    # 6 requests over the single token available up front
+    assert time.perf_counter() - started >= 5 / 20.0 * 0.9
+
+    try:
+        RateLimiter(0.0)
+    except ValueError:
+        pass
+    else:
+        raise AssertionError('expected ValueError')
+
+async def test_shared() -> None:
+    # a burst lets the first requests through at once
+    limiter = RateLimiter(10.0, burst=6)
+    server = MockServer(latency=0.05)
+    pool = AkinatorPool(transport=server, rate_limiter=limiter)
+
+    started = time.perf_counter()
+    await asyncio.gather(*(pool.create(key).start_game() for key in range(2)))
+    assert server.requests == ['new_session', 'new_session']
+    assert server.max_concurrent_requests > 1, server.max_concurrent_requests
+
+    # while the rest wait on the limiter shared by every game of the pool
+    await asyncio.gather(*(pool.get(key).answer(Answer.Yes) for key in range(2)))
+    assert time.perf_counter() - started >= 1 / 10.0 * 0.9
+
+async def test_global() -> None:
+    akinator.set_rate_limiter(RateLimiter(20.0))
+    try:
+        assert akinator.get_rate_limiter().rate == 20.0
+        games = [AsyncAkinator(transport=MockServer()) for _ in range(2)]
+
+        started = time.perf_counter()
+        await asyncio.gather(*(aki.start_game() for aki in games))
+        assert time.perf_counter() - started >= 5 / 20.0 * 0.9
+    finally:
+        akinator.set_rate_limiter(None)
+
+    assert akinator.get_rate_limiter() is None
+
+if __name__ == '__main__':
+    test_rate_limiter()
+    asyncio.run(test_shared())
+    asyncio.run(test_global())
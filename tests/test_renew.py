@@ -0,0 +1,119 @@
+import asyncio
+import time
+
+from akinator import (
+    AsyncAkinator,
+    TimeoutError,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def test_idle() -> None:
+    aki = Akinator(transport=MockServer(), session_timeout=0)
+    assert aki.idle_seconds is None
+    assert not aki.is_expired
+
+    aki.start_game()
+    idle = aki.idle_seconds
+    assert idle >= 0.0
+
+    # any time spent idle is longer than a session timeout of 0
+    time.sleep(0.01)
+    assert aki.idle_seconds > idle
+    assert aki.is_expired
+
+    # while a long enough timeout keeps the session alive
+    aki.session_timeout = 300
+    assert not aki.is_expired
+
+    # the last activity and the session settings are kept when saving and restoring a game
+    restored = Akinator.from_state(aki.to_json(), transport=MockServer())
+    assert restored.idle_seconds > idle
+    assert restored.session_timeout == 300
+    assert not restored.auto_renew
+
+def test_renew_on_timeout() -> None:
+    server = MockServer()
+    aki = Akinator(transport=server, auto_renew=True)
+    aki.start_game()
+    aki.answer(Answer.Yes)
+    aki.answer(Answer.No)
+    question = aki.question
+
+    server.expire_sessions()
+    aki.answer(Answer.Idk)
+
+    # the answers were replayed into a new session, before answering again
+    assert server.sessions == 2
+    assert server.requests[-4:] == ['new_session', 'answer_api', 'answer_api', 'answer_api']
+    assert aki.step == 3
+    assert [entry.question for entry in aki.history][-1] == question
+    assert [entry.answer for entry in aki.history] == [Answer.Yes, Answer.No, Answer.Idk]
+
+    # without auto renewal the timeout is raised as is
+    aki.auto_renew = False
+    server.expire_sessions()
+    try:
+        aki.answer(Answer.Yes)
+    except TimeoutError:
+        pass
+    else:
+        raise AssertionError('expected TimeoutError')
+
+def test_renew_when_expired() -> None:
+    server = MockServer()
+    aki = Akinator(transport=server, auto_renew=True)
+    aki.start_game()
+    aki.answer(Answer.Yes)
+
+    # any time spent idle is longer than a session timeout of 0
+    aki.session_timeout = 0
+    time.sleep(0.01)
+    aki.back()
+
+    # the expired session is renewed up front, rather than after a failed request
+    assert server.requests == ['new_session', 'answer_api', 'new_session', 'answer_api', 'cancel_answer']
+    assert aki.step == 0
+
+def test_renew_after_reject() -> None:
+    server = MockServer(guesses=[{'name': 'Mario'}, {'name': 'Luigi'}])
+    aki = Akinator(transport=server)
+    aki.start_game()
+    aki.answer(Answer.Yes)
+
+    assert aki.win().name == 'Mario'
+    aki.reject_guess()
+    aki.answer(Answer.No)
+
+    server.expire_sessions()
+    aki.renew()
+
+    # the rejection is replayed at the step it was made at, between the answers
+    assert server.requests[-4:] == ['new_session', 'answer_api', 'exclusion', 'answer_api']
+    assert aki.step == 3
+    assert [guess.name for guess in aki.rejected_guesses] == ['Mario']
+
+    # so the rejected guess is not proposed again
+    assert aki.win().name == 'Luigi'
+
+async def test_async_renew() -> None:
+    server = MockServer()
+    aki = AsyncAkinator(transport=server)
+    await aki.start_game()
+    await aki.answer(Answer.Yes)
+
+    server.expire_sessions()
+    await aki.renew()
+    assert server.sessions == 2
+    assert aki.step == 1
+
+    await aki.answer(Answer.No)
+    assert aki.step == 2
+
+if __name__ == '__main__':
+    test_idle()
+    test_renew_on_timeout()
+    test_renew_when_expired()
+    test_renew_after_reject()
+    asyncio.run(test_async_renew())
@@ -0,0 +1,81 @@
+import asyncio
+
+from akinator import (
+    TechnicalError,
+    AsyncAkinator,
+    RetryPolicy,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def policy(**kwargs) -> RetryPolicy:
+    return RetryPolicy(base_delay=0.01, max_delay=0.05, **kwargs)
+
+def test_retries_transient_errors() -> None:
+    server = MockServer()
+    aki = Akinator(transport=server, retry=policy())
+
+    aki.start_game()
+
+    server.inject_error('TechnicalError', times=2)
+    aki.answer(Answer.Yes)
+
+    # the answer is only applied once, even though it was sent three times
+    assert aki.step == 1
+    assert server.requests == ['new_session'] + ['answer_api'] * 3
+
+def test_gives_up() -> None:
+    server = MockServer()
+    aki = Akinator(transport=server, retry=policy(max_attempts=2))
+    aki.start_game()
+
+    server.inject_error('TechnicalError', times=2)
+    try:
+        aki.answer(Answer.Yes)
+    except TechnicalError:
+        pass
+    else:
+        raise AssertionError('expected TechnicalError')
+
+    assert aki.step == 0
+
+def test_retry_on() -> None:
+    server = MockServer()
+    aki = Akinator(transport=server, retry=policy(retry_on=['ServersDown']))
+    aki.start_game()
+
+    server.inject_error('TechnicalError')
+    try:
+        aki.answer(Answer.Yes)
+    except TechnicalError:
+        pass
+    else:
+        raise AssertionError('expected TechnicalError')
+
+    try:
+        RetryPolicy(retry_on=['NoMoreQuestions'])
+    except ValueError:
+        pass
+    else:
+        raise AssertionError('expected ValueError')
+
+    aki.retry = None
+    assert aki.retry is None
+
+async def test_async_retries() -> None:
+    server = MockServer()
+    aki = AsyncAkinator(transport=server, retry=policy())
+
+    server.inject_error('ServersDown')
+    await aki.start_game()
+    await aki.answer(Answer.No)
+
+    assert aki.step == 1
+    assert aki.retry.max_attempts == 3
+
+if __name__ == '__main__':
+    test_retries_transient_errors()
+    test_gives_up()
+    test_retry_on()
+    asyncio.run(test_async_retries())
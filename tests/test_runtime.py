@@ -0,0 +1,56 @@
+import asyncio
+import os
+
+import akinator
+from akinator import (
+    AsyncAkinator,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def thread_names() -> list:
+    names = []
+    for task in os.listdir('/proc/self/task'):
+        with open(f'/proc/self/task/{task}/comm') as file:
+            names.append(file.read().strip())
+
+    return names
+
+async def play_async() -> int:
+    aki = AsyncAkinator(transport=MockServer())
+    await aki.start_game()
+    await aki.answer(Answer.No)
+
+    return aki.step
+
+def test() -> None:
+    try:
+        akinator.configure_runtime(worker_threads=0)
+    except ValueError:
+        pass
+    else:
+        raise AssertionError('expected ValueError')
+
+    akinator.configure_runtime(worker_threads=2, thread_name='aki-worker', enable_io_driver=False)
+
+    aki = Akinator(transport=MockServer())
+    aki.start_game()
+    aki.answer(Answer.Yes)
+    assert aki.step == 1
+
+    # the async games run on the same runtime
+    assert asyncio.run(play_async()) == 1
+
+    if os.path.isdir('/proc/self/task'):
+        assert thread_names().count('aki-worker') == 2
+
+    try:
+        akinator.configure_runtime(worker_threads=4)
+    except RuntimeError:
+        pass
+    else:
+        raise AssertionError('expected RuntimeError')
+
+if __name__ == '__main__':
+    test()
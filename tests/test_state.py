@@ -0,0 +1,193 @@
+import asyncio
+import pickle
+import time
+
+from akinator import (
+    CantGoBackAnyFurther,
+    InvalidGameState,
+    NoMoreQuestions,
+    AsyncAkinator,
+    GameState,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def test_transitions() -> None:
+    aki = Akinator(transport=MockServer(guesses=[{'name': 'Mario'}]))
+    assert aki.state == GameState.NotStarted
+
+    try:
+        aki.answer(Answer.Yes)
+    except InvalidGameState as error:
+        assert isinstance(error, RuntimeError)
+        assert error.current == GameState.NotStarted
+        assert error.required == [GameState.InProgress]
+    else:
+        raise AssertionError('expected InvalidGameState')
+
+    aki.start_game()
+    assert aki.state == GameState.InProgress
+    aki.answer(Answer.Yes)
+
+    aki.win()
+    assert aki.state == GameState.Guessing
+
+    try:
+        aki.answer(Answer.Yes)
+    except InvalidGameState as error:
+        assert error.current == GameState.Guessing
+    else:
+        raise AssertionError('expected InvalidGameState')
+
+    # going back discards the guess
+    aki.back()
+    assert aki.state == GameState.InProgress
+    assert aki.first_guess is None
+
+    aki.win()
+    aki.confirm_guess()
+    assert aki.state == GameState.Won
+
+    aki.start_game()
+    assert aki.state == GameState.InProgress
+
+    aki.close()
+    assert aki.state == GameState.Closed
+
+def test_lost() -> None:
+    aki = Akinator(transport=MockServer(guesses=[{'name': 'Mario'}]))
+    aki.start_game()
+
+    aki.win()
+    aki.reject_guess()
+
+    # no guesses are left once the only one was rejected
+    try:
+        aki.win()
+    except NoMoreQuestions:
+        pass
+    else:
+        raise AssertionError('expected NoMoreQuestions')
+
+    assert aki.state == GameState.Lost
+    assert Akinator.from_state(aki.to_dict()).state == GameState.Lost
+
+    try:
+        aki.reject_guess()
+    except InvalidGameState as error:
+        assert error.current == GameState.Lost
+        assert error.required == [GameState.Guessing]
+    else:
+        raise AssertionError('expected InvalidGameState')
+
+    # running out of questions loses the game as well
+    aki = Akinator(transport=MockServer(questions=['only']))
+    aki.start_game()
+
+    try:
+        aki.answer(Answer.Yes)
+    except NoMoreQuestions:
+        pass
+    else:
+        raise AssertionError('expected NoMoreQuestions')
+
+    assert aki.state == GameState.Lost
+
+    # where going back is refused like any other operation
+    try:
+        aki.back()
+    except InvalidGameState as error:
+        assert error.current == GameState.Lost
+        assert error.required == [GameState.InProgress, GameState.Guessing]
+    else:
+        raise AssertionError('expected InvalidGameState')
+
+def test_back() -> None:
+    aki = Akinator(transport=MockServer())
+
+    # the state of the game is checked before the step
+    try:
+        aki.back()
+    except InvalidGameState as error:
+        assert error.current == GameState.NotStarted
+    else:
+        raise AssertionError('expected InvalidGameState')
+
+    aki.start_game()
+
+    try:
+        aki.back()
+    except CantGoBackAnyFurther:
+        pass
+    else:
+        raise AssertionError('expected CantGoBackAnyFurther')
+
+def test_expired() -> None:
+    server = MockServer()
+    aki = Akinator(transport=server, session_timeout=0)
+    aki.start_game()
+
+    # going idle for longer than the session timeout is only reported, the requests are still sent
+    time.sleep(0.01)
+    assert aki.state == GameState.Expired
+    aki.answer(Answer.Yes)
+
+    server.expire_sessions()
+
+    try:
+        aki.answer(Answer.Yes)
+    except TimeoutError:
+        pass
+    else:
+        raise AssertionError('expected TimeoutError')
+
+    # the session is refused once the server has timed it out
+    try:
+        aki.answer(Answer.Yes)
+    except InvalidGameState as error:
+        assert error.current == GameState.Expired
+    else:
+        raise AssertionError('expected InvalidGameState')
+
+    assert Akinator.from_state(aki.to_dict()).state == GameState.Expired
+
+    aki.renew()
+    aki.session_timeout = 300
+    assert aki.state == GameState.InProgress
+    aki.answer(Answer.Yes)
+    assert aki.step == 2
+
+    # timed out sessions are renewed ahead of the request when renewing automatically
+    server.expire_sessions()
+    aki.auto_renew = True
+    aki.answer(Answer.No)
+    assert aki.step == 3
+    assert server.sessions == 3
+
+def test_enum() -> None:
+    assert str(GameState.Guessing) == 'Guessing'
+    assert pickle.loads(pickle.dumps(GameState.Won)) == GameState.Won
+
+async def test_async_state() -> None:
+    aki = AsyncAkinator(transport=MockServer())
+    assert aki.state == GameState.NotStarted
+
+    try:
+        await aki.win()
+    except InvalidGameState as error:
+        assert error.required == [GameState.InProgress, GameState.Guessing]
+    else:
+        raise AssertionError('expected InvalidGameState')
+
+    await aki.start_game()
+    await aki.win()
+    assert aki.state == GameState.Guessing
+
+if __name__ == '__main__':
+    test_transitions()
+    test_lost()
+    test_back()
+    test_expired()
+    test_enum()
+    asyncio.run(test_async_state())
@@ -0,0 +1,60 @@
+import asyncio
+
+from akinator import (
+    AsyncAkinator,
+    RetryPolicy,
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def test_stats() -> None:
+    server = MockServer(latency=0.01)
+    aki = Akinator(transport=server, retry=RetryPolicy(base_delay=0.01, max_delay=0.05))
+
+    aki.start_game()
+    aki.answer(Answer.Yes)
+
+    server.inject_error('TechnicalError')
+    aki.answer(Answer.No)
+    aki.back()
+
+    stats = aki.stats
+    assert stats.requests['new_session'] == 1
+    assert stats.requests['answer_api'] == 3
+    assert stats.requests['cancel_answer'] == 1
+    assert stats.failures['answer_api'] == 1
+    assert sum(stats.failures.values()) == 1
+    assert stats.total_requests == sum(stats.requests.values())
+
+    assert len(stats.latencies['answer_api']) == 3
+    assert all(latency >= 0.01 for latency in stats.latencies['answer_api'])
+    assert abs(stats.total_latency - sum(sum(latencies) for latencies in stats.latencies.values())) < 1e-9
+
+    assert stats.retries == 1
+    assert stats.backs == 1
+    assert [step for step, _ in stats.progression] == [0, 1, 2, 1]
+
+    assert stats.to_dict()['backs'] == 1
+
+    # the stats are reset when a new game is started
+    aki.start_game()
+    assert aki.stats.backs == 0
+    assert aki.stats.requests['new_session'] == 1
+    assert aki.stats.progression == [(0, aki.progression)]
+
+async def test_async_stats() -> None:
+    aki = AsyncAkinator(transport=MockServer())
+
+    await aki.start_game()
+    await aki.answer(Answer.Yes)
+    await aki.back()
+
+    stats = aki.stats
+    assert stats.requests['answer_api'] == 1
+    assert stats.backs == 1
+    assert stats.retries == 0
+
+if __name__ == '__main__':
+    test_stats()
+    asyncio.run(test_async_stats())
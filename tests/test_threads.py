@@ -0,0 +1,29 @@
+from concurrent.futures import ThreadPoolExecutor
+
+from akinator import (
+    MockServer,
+    Akinator,
+    Answer,
+)
+
+def play(aki: Akinator) -> int:
+    aki.start_game()
+    aki.answer(Answer.Yes)
+
+    return aki.step
+
+def test() -> None:
+    # each request takes a while to be answered, so that the requests of the games overlap
+    # if (and only if) they are not holding the GIL while waiting on the server
+    server = MockServer(latency=0.2)
+    games = [Akinator(transport=server) for _ in range(8)]
+
+    with ThreadPoolExecutor(max_workers=len(games)) as executor:
+        steps = list(executor.map(play, games))
+
+    assert steps == [1] * len(games)
+    assert server.max_concurrent_requests > 1, server.max_concurrent_requests
+    assert server.in_flight_requests == 0
+
+if __name__ == '__main__':
+    test()